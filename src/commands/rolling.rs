@@ -13,15 +13,30 @@ use serenity::{
     },
     prelude::*,
 };
-use std::collections::HashMap;
+use rand::thread_rng;
+use std::{collections::HashMap, time::Duration};
 use crate::{
     dice::{
-        command_translations,
+        command_translations::{self, BladesRoll},
+        dice_errors::RollError,
+        simulation::{self, Simulation},
+        genesymbols::GeneSymbol,
         tray::Tray, roll::Roll,
+        roll_value::RollValue,
+        roll_token::RollToken,
+        token_kinds::{Ironsworn, IronswornScore, Operator, Pbta, Target},
     }, 
+    config::Config,
     sixball_errors::SixballError
 };
 
+// How many "at least" lines the odds command lists when not given a threshold, and how many bars go in a sim histogram
+const ODDS_LINES: usize = 12;
+// Length of the longest histogram bar
+const HISTOGRAM_WIDTH: usize = 20;
+const SIM_DEFAULT_TRIALS: usize = 10_000;
+const SIM_BUDGET: Duration = Duration::from_secs(3);
+
 pub type TrayMap = HashMap<TrayId, Tray>;
 
 #[derive(Debug, Hash, PartialEq, Eq)]
@@ -32,7 +47,7 @@ pub enum TrayId {
 
 #[command]
 #[description="The basic roll command!\n
-Use standard die roll notation of the form `XdY`. I can roll up to 255 dice with up to 255 sides at once!\n
+Use standard die roll notation of the form `XdY`. I can roll up to 65535 dice with up to 65535 sides at once!\n
 I can also do math with dice! (　-\\`ω-)✧ﾄﾞﾔｯ Just plug your dice into any math expression, e.g. `1d20+5`. If the `calc` command can handle it, so can the `roll` command!\n
 Additional dice operations are added as the Boss thinks of them and has time. Please wait warmly!\n
 Want to roll the same kind of roll multiple times in one go? Put the number in front of the roll separated by a hash (#)! Like this: `6#3d6`.
@@ -50,7 +65,7 @@ async fn roll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     };
     let in_command = &roll_command;
 
-    let response = match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true).await {
+    let response = match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
         Ok(res) => format!("{}", res),
         Err(why) => format!("{}", why),
     };
@@ -135,6 +150,131 @@ async fn modify(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+/// Push your latest Year Zero roll! ٩(๑`^´๑)۶
+/// 
+/// Rerolls every die that isn't showing a 6 or a 1 and counts successes again. The pushed roll goes into \$
+/// the tray as a revision of the original, just like with `modify`. Each roll can only be pushed once.
+#[command]
+#[aliases("yzpush")]
+async fn push(ctx: &Context, msg: &Message) -> CommandResult {
+    // Get context data with write permission to manipulate the tray
+    let mut tray_data = ctx.data.write().await;
+    let mut tray_map = tray_data
+        .get_mut::<crate::TrayKey>()
+        .expect("Failed to retrieve tray map!")
+        .lock().await;
+
+    let tray = match tray_map.get_mut(&make_tray_id(msg)) {
+        Some(tray) => tray,
+        None => {
+            msg.reply_ping(&ctx.http, "There's nothing to push!").await?;
+            return Ok(());
+        }
+    };
+    let latest_command = match tray.get_newest_roll() {
+        Ok(roll) => roll.command().to_string(),
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        }
+    };
+    let (base, skill, _) = match command_translations::year_zero_pools(&latest_command) {
+        Some(pools) => pools,
+        None => {
+            msg.reply_ping(&ctx.http, "The latest roll isn't a Year Zero roll!").await?;
+            return Ok(());
+        }
+    };
+    if latest_command.ends_with(command_translations::YEAR_ZERO_PUSH) {
+        msg.reply_ping(&ctx.http, "That roll has already been pushed!").await?;
+        return Ok(());
+    }
+
+    let pusher = msg.author_nick(&ctx).await.unwrap_or(msg.author.name.clone());
+    let response = match tray.modify_latest(command_translations::YEAR_ZERO_PUSH, "", &pusher) {
+        Ok(pushed_roll) => {
+            let prepend = match year_zero_outcome(pushed_roll, base, skill).as_str() {
+                "" => "\n".to_string(),
+                outcome => format!("\n{}: ", outcome),
+            };
+            format!("Pushed roll: `{}`{}", pushed_roll.command(), roll_format_discord(pushed_roll, true, &prepend))
+        },
+        Err(why) => format!("{}", why),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Burn momentum on your latest Ironsworn action roll! ٩(ˊᗜˋ*)و
+/// 
+/// Give me your momentum and I'll use it in place of your action score against the same challenge dice:
+/// > ~burn 8
+/// The new outcome goes into the tray as a revision of the original, just like with `modify`. \$
+/// You can only burn momentum once per roll, and only if it beats your action score.
+#[command]
+#[aliases("momentum")]
+async fn burn(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let momentum = match args.single::<u16>() {
+        Ok(momentum) => momentum,
+        Err(_) => {
+            msg.reply_ping(&ctx.http, "How much momentum are you burning?").await?;
+            return Ok(());
+        }
+    };
+
+    // Get context data with write permission to manipulate the tray
+    let mut tray_data = ctx.data.write().await;
+    let mut tray_map = tray_data
+        .get_mut::<crate::TrayKey>()
+        .expect("Failed to retrieve tray map!")
+        .lock().await;
+
+    let tray = match tray_map.get_mut(&make_tray_id(msg)) {
+        Some(tray) => tray,
+        None => {
+            msg.reply_ping(&ctx.http, "There's nothing to burn momentum on!").await?;
+            return Ok(());
+        }
+    };
+    let latest = match tray.get_newest_roll() {
+        Ok(roll) => roll.operations().iter().rev().find_map(|operation| match operation {
+            RollToken::Operator(Operator::Ironsworn(ironsworn)) => Some((ironsworn.kind, ironsworn.score)),
+            _ => None,
+        }),
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        }
+    };
+    let rejection = match latest {
+        Some((IronswornScore::Action, score)) if momentum <= score => Some(format!("Burning {} momentum wouldn't beat your action score of {}!", momentum, score)),
+        Some((IronswornScore::Action, _)) => None,
+        Some((IronswornScore::Momentum, _)) => Some("You've already burned momentum on that roll!".to_string()),
+        Some((IronswornScore::Progress, _)) => Some("You can't burn momentum on a progress roll!".to_string()),
+        None => Some("The latest roll isn't an Ironsworn roll!".to_string()),
+    };
+    if let Some(rejection) = rejection {
+        msg.reply_ping(&ctx.http, rejection).await?;
+        return Ok(());
+    }
+
+    let burner = msg.author_nick(&ctx).await.unwrap_or(msg.author.name.clone());
+    let response = match tray.modify_latest(&format!("ism{}", momentum), "", &burner) {
+        Ok(burned_roll) => {
+            let prepend = match ironsworn_outcome(burned_roll).as_str() {
+                "" => "\n".to_string(),
+                outcome => format!("\n{}: ", outcome),
+            };
+            format!("Burned momentum: `{}`{}", burned_roll.command(), roll_format_discord(burned_roll, true, &prepend))
+        },
+        Err(why) => format!("{}", why),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
 #[command]
 #[aliases("tray")]
 async fn pastrolls(ctx: &Context, msg: &Message) -> CommandResult {
@@ -144,6 +284,8 @@ async fn pastrolls(ctx: &Context, msg: &Message) -> CommandResult {
         .expect("Failed to retrieve tray map!")
         .lock().await;
 
+    let emoji = genesys_emoji(tray_data.get::<crate::ConfigKey>().expect("Failed to retrieve config!"));
+
     if let Some(tray) = tray_map.get(&make_tray_id(msg)) {
         msg.channel_id.send_message(&ctx.http, |m| {
             m.embed(|e| {
@@ -151,7 +293,10 @@ async fn pastrolls(ctx: &Context, msg: &Message) -> CommandResult {
                 for (i, roll) in tray.rolls().iter().enumerate() {
                     // Build the title here containing i, person who rolled, and maybe timestamp?
                     let title = format!("{}: By {} at {}", i, roll.roller(), roll.timestamp().format("%y/%m/%d %H:%M:%S"));
-                    let text = format!("{}", roll);
+                    let text = match genesys_summary(roll, &emoji) {
+                        Some(summary) => format!("{}\n{}", roll, summary),
+                        None => format!("{}", roll),
+                    };
                     e.field(title, text, false);
                 }
                 e
@@ -173,6 +318,7 @@ async fn verbose(ctx: &Context, msg: &Message) -> CommandResult {
         .get::<crate::TrayKey>()
         .expect("Failed to retrieve tray map!")
         .lock().await;
+    let emoji = genesys_emoji(tray_data.get::<crate::ConfigKey>().expect("Failed to retrieve config!"));
 
     if let Some(tray) = tray_map.get(&make_tray_id(msg)) {
         let latest_roll = match tray.get_newest_roll() {
@@ -197,6 +343,10 @@ async fn verbose(ctx: &Context, msg: &Message) -> CommandResult {
                     e.field(name, value, false);
                 }
                 e.field("Total", latest_roll.result(), false);
+                if let Some(summary) = genesys_summary(latest_roll, &emoji) {
+                    e.field("Outcome", summary, false);
+                }
+                e.footer(|f| f.text(format!("Seed: {}", latest_roll.seed())));
                 e
             });
             m
@@ -208,6 +358,167 @@ async fn verbose(ctx: &Context, msg: &Message) -> CommandResult {
     Ok(())
 }
 
+#[command]
+#[description="Replays a roll from its seed, so you can check that I didn't cheat! (｀・ω・´)ゞ\n
+Without arguments, I'll replay the latest roll in the tray from the seed it was rolled with (you can find it at the bottom of the `verbose` output).
+You can also give me a seed followed by a roll string, like this: `~replay 1234567 4d6k3`. The same seed and roll string will always give the same dice."]
+async fn replay(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let replayed = if args.message().trim().is_empty() {
+        let tray_data = ctx.data.read().await;
+        let tray_map = tray_data
+            .get::<crate::TrayKey>()
+            .expect("Failed to retrieve tray map!")
+            .lock().await;
+
+        match tray_map.get(&make_tray_id(msg)) {
+            Some(tray) => tray.get_newest_roll().and_then(|roll| roll.replay()),
+            None => {
+                msg.reply_ping(&ctx.http, "There's nothing to replay!").await?;
+                return Ok(());
+            },
+        }
+    } else {
+        let (seed, roll_command) = args.message().trim().split_once(char::is_whitespace).unwrap_or((args.message().trim(), ""));
+        let roller = msg.author_nick(&ctx).await.unwrap_or(msg.author.name.clone());
+        match seed.parse() {
+            Ok(seed) => Roll::new_seeded(&roll_command.to_lowercase(), "", &roller, seed),
+            Err(why) => Err(RollError::ParseError(why)),
+        }
+    };
+
+    let response = match replayed {
+        Ok(roll) => format!("Replayed `{}` with seed {}:{}", roll.command(), roll.seed(), roll_format_discord(&roll, true, "\n")),
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[description="Tells you your odds before you commit to a roll! (๑•̀ㅂ•́)و✧\n
+Give me a roll string, like `~odds 4d6k3`, and instead of rolling it I'll work out every way it could come out and tell you the mean, the standard deviation, and how likely you are to get at least each result.
+If you only care about one number, add it after `>=`, like this: `~odds 2d6+1 >= 8`.\n
+I can work out sums, keep, rerolls, explosions and success counting exactly. For anything else (like Genesys dice), I'll roll it a whole bunch of times and give you my best estimate instead!"]
+#[aliases("stats", "prob")]
+async fn odds(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (roll_command, threshold) = match extract_threshold(args.message()) {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", SixballError::RollError(why))).await?;
+            return Ok(());
+        },
+    };
+
+    // Working out the odds can take a moment for big pools, so keep it off the async threads
+    let in_command = roll_command.clone();
+    let calculated = tokio::task::spawn_blocking(move || {
+        simulation::exact_or_simulated(&in_command, SIM_DEFAULT_TRIALS, SIM_BUDGET, &mut thread_rng())
+    }).await?;
+
+    let response = match calculated {
+        Ok((distribution, simulated)) => {
+            let mut response = format!("Odds for `{}`: {}, range {} to {}", roll_command, distribution, distribution.min(), distribution.max());
+            match threshold {
+                Some(threshold) => response.push_str(&format!("\nChance of ≥ {}: {:.2}%", threshold, distribution.p_at_least(threshold) * 100.0)),
+                None => {
+                    let outcomes = distribution.outcomes();
+                    let step = outcomes.len().div_ceil(ODDS_LINES);
+                    for (value, _) in outcomes.iter().step_by(step.max(1)) {
+                        response.push_str(&format!("\n≥ {}: {:.2}%", value, distribution.p_at_least(*value) * 100.0));
+                    }
+                },
+            }
+            if let Some(trials) = simulated {
+                response.push_str(&format!("\n(That one's too much for me to work out exactly, so this is estimated from {} rolls! (；・∀・))", trials));
+            }
+            response
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+#[command]
+#[description="Rolls the same thing over and over to see how it turns out on average! ٩(ˊᗜˋ*)و\n
+Give me the number of times to roll followed by a roll string, like `~sim 10000 (8)d10er10t8`. If you leave out the number, I'll roll it 10000 times. I'll stop early if it takes me too long, though!
+I'll tell you the average with a 95% confidence interval, some percentiles, and a histogram of the results. Add `>= N` at the end to also get the chance of rolling at least N.\n
+Genesys dice are counted by net successes (triumphs count as successes and despairs as failures)."]
+#[aliases("simulate", "montecarlo")]
+async fn sim(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (trials, message) = match args.message().trim().split_once(char::is_whitespace) {
+        Some((trials, rest)) => match trials.parse::<usize>() {
+            Ok(trials) => (trials.min(simulation::MAX_TRIALS), rest),
+            Err(_) => (SIM_DEFAULT_TRIALS, args.message()),
+        },
+        None => (SIM_DEFAULT_TRIALS, args.message()),
+    };
+    let (roll_command, threshold) = match extract_threshold(message) {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", SixballError::RollError(why))).await?;
+            return Ok(());
+        },
+    };
+
+    let in_command = roll_command.clone();
+    let simulated = tokio::task::spawn_blocking(move || {
+        Simulation::run(&in_command, trials, SIM_BUDGET, &mut thread_rng())
+    }).await?;
+
+    let response = match simulated {
+        Ok(simulation) => {
+            let (mean_low, mean_high) = simulation.mean_interval();
+            let mut response = format!(
+                "Rolled `{}` {} times{}: mean {:.2} (95% CI {:.2} to {:.2}), standard deviation {:.2}, range {} to {}",
+                roll_command,
+                simulation.trials(),
+                if simulation.cut_short() { " (ran out of time!)" } else { "" },
+                simulation.mean(),
+                mean_low,
+                mean_high,
+                simulation.std_dev(),
+                simulation.min(),
+                simulation.max(),
+            );
+            response.push_str(&format!(
+                "\nPercentiles: 5th {}, 25th {}, median {}, 75th {}, 95th {}",
+                simulation.percentile(5.0),
+                simulation.percentile(25.0),
+                simulation.percentile(50.0),
+                simulation.percentile(75.0),
+                simulation.percentile(95.0),
+            ));
+            if let Some(threshold) = threshold {
+                let (p_low, p_high) = simulation.p_at_least_interval(threshold);
+                response.push_str(&format!(
+                    "\nChance of ≥ {}: {:.2}% (95% CI {:.2}% to {:.2}%)",
+                    threshold,
+                    simulation.p_at_least(threshold) * 100.0,
+                    p_low * 100.0,
+                    p_high * 100.0,
+                ));
+            }
+            let histogram = simulation.histogram(ODDS_LINES);
+            let tallest = histogram.iter().map(|(_, _, count)| *count).max().unwrap_or(1);
+            response.push_str("\n```");
+            for (low, high, count) in histogram {
+                let label = if low == high { format!("{}", low) } else { format!("{:.1}–{:.1}", low, high) };
+                let bar = "█".repeat(count * HISTOGRAM_WIDTH / tallest);
+                response.push_str(&format!("\n{:>12} {:>6.2}% {}", label, count as f64 / simulation.trials() as f64 * 100.0, bar));
+            }
+            response.push_str("\n```");
+            response
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
 /// Specialty command for CofD/nWoD rolls! ｶﾀｶﾀｶﾀ(((;ﾟ;Д;ﾟ;)))ｶﾀｶﾀｶﾀ
 /// It was written with CofD/nWoD 2e in mind, but should be backwards compatible with 1e.
 /// 
@@ -254,7 +565,7 @@ async fn cofd(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     };
 
     let response = match command_translations::cofd(&in_command) {
-        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true).await {
+        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
             Ok(res) => format!("{}", res),
             Err(why) => format!("{}", why),
         },
@@ -265,20 +576,164 @@ async fn cofd(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+/// Specialty command for classic World of Darkness rolls! (ง •̀_•́)ง
+/// 
+/// Give me your dice pool and I'll roll that many d10s against difficulty 6, e.g.:
+/// > ~wod 7
+/// 1s cancel successes, and if you get no successes but at least one 1, that's a botch.
+/// 
+/// To set the difficulty, use `diff` (or just `d`) followed by the number:
+/// > ~wod 5+2 diff 8
+/// 
+/// If your specialty applies, add `spec` and 10s will count as two successes:
+/// > ~wod 6 d7 spec
+/// 
+/// Like cofd, you can add successes after rolling by putting them after a semicolon:
+/// > ~wod 6; +1
+#[command]
+#[aliases("owod", "v20")]
+async fn wod(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::wod(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&wod_outcome)).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Ironsworn and Starforged rolls! (•̀ᴗ•́)و
+/// 
+/// Give me your stat and any adds, and I'll roll the action die against two challenge dice, e.g.:
+/// > ~ironsworn 2 +1
+/// Your action score (capped at 10) has to beat a challenge die to count against it: beat both for a strong hit, \$
+/// one for a weak hit, and neither for a miss. If the challenge dice match, I'll let you know.
+/// 
+/// For a progress roll, give me the number of filled boxes on your progress track instead:
+/// > ~ironsworn progress 7
+/// 
+/// To burn momentum on your latest action roll, use `~burn` with your momentum.
+#[command]
+#[aliases("is", "starforged")]
+async fn ironsworn(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::ironsworn(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&ironsworn_outcome)).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Legend of the Five Rings rolls! (｀・ω・´)ゞ
+/// 
+/// ### Roll and Keep (4e)
+/// Give me your roll in the usual `XkY` form, e.g.:
+/// > ~l5r 6k3
+/// This rolls 6d10, explodes 10s (adding onto the die that exploded), and keeps the 3 highest. \$
+/// Anything else you write gets added to the total, so `~l5r 6k3+5` works as you'd expect.
+/// 
+/// The Ten Dice Rule is applied for you: every two rolled dice past 10 become a kept die, \$
+/// and every kept die past 10 becomes a +2 bonus. So `~l5r 14k9` is rolled as 10k10+2.
+/// 
+/// Add `e` for an emphasis (reroll 1s once) or `u` for an unskilled roll (10s don't explode):
+/// > ~l5r 7k4e
+/// 
+/// ### Narrative Dice (5e)
+/// Without an `XkY`, the command rolls 5e ring and skill dice instead. Use `r` followed by \$
+/// your ring and `s` followed by your skill:
+/// > ~l5r r3 s2
+/// Explosive successes roll another die of the same kind. All the dice and their symbols are \$
+/// listed in the breakdown so you can pick which ones to keep.
 #[command]
 #[aliases("l5roll", "rings")]
-async fn l5r(ctx: &Context, msg: &Message) -> CommandResult {
-    let roll = format!("{} I'm not weeb enough for that yet!", msg.author);
-    msg.channel_id.say(&ctx.http, roll).await?;
+async fn l5r(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::l5r(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
 
     Ok(())
 }
 
+/// Specialty command for Shadowrun rolls! (⌐■_■)
+/// 
+/// ### Basic Usage
+/// For basic usage, just supply the size of your dice pool, e.g.:
+/// > ~sr 12
+/// This will roll 12 dice and count hits on 5s and 6s. If more than half the dice come up 1s, \$
+/// that's a glitch, and if you didn't get any hits on top of that, it's a critical glitch. \$
+/// Either way, it'll show up in the breakdown (and in `verbose`).
+/// 
+/// Like the other specialty commands, the pool can be any mathematical expression:
+/// > ~sr 6+4-2
+/// 
+/// ### Limits
+/// To cap your hits at a limit, add `l` followed by the limit:
+/// > ~sr 12 l5
+/// Dice still count toward glitches even when their hits get cut off by the limit.
+/// 
+/// ### Edge
+/// To push the limit, add the `e` flag:
+/// > ~sr 12+4 e
+/// This applies the Rule of Six (6s explode until they stop coming up) and ignores any limit. \$
+/// Remember to add your Edge to the pool yourself!
+/// 
+/// You can also add hits after rolling (or do other math), by putting it after a semicolon:
+/// > ~sr 12 l5; +1
 #[command]
-#[aliases("sroll")]
-async fn sr(ctx: &Context, msg: &Message) -> CommandResult {
-    let roll = format!("{} I'm not shady enough for that yet!", msg.author);
-    msg.channel_id.say(&ctx.http, roll).await?;
+#[aliases("sroll", "shadowrun")]
+async fn sr(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::shadowrun(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
 
     Ok(())
 }
@@ -331,7 +786,7 @@ async fn exroll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     };
 
     let response = match command_translations::exalted(&in_command) {
-        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true).await {
+        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
             Ok(res) => format!("{}", res),
             Err(why) => format!("{}", why),
         },
@@ -353,7 +808,13 @@ The dice codes are:
 \t• p: Proficiency (Yellow d12)
 \t• s: Setback (Black d6)
 \t• d: Difficulty (Purple d8)
-\t• c: Challenge (Red d12)\n
+\t• c: Challenge (Red d12)
+\t• f: Force (White d12, for Star Wars)\n
+You can also upgrade and downgrade dice with the same format, e.g. `~genroll a2 d2 u1` upgrades one Ability die to Proficiency. If there's nothing to upgrade, you get a new Ability or Difficulty die instead, and if there's nothing to downgrade, nothing happens. I'll tell you which dice I ended up rolling!
+\t• u: Upgrade Ability to Proficiency
+\t• ud: Upgrade Difficulty to Challenge
+\t• dg: Downgrade Proficiency to Ability
+\t• dgd: Downgrade Challenge to Difficulty\n
 Documentation can be found [here](https://github.com/LittleJustices/Rustball/blob/master/ROLLSYNTAX.md#genroll-genesys-narrative-dice)!"]
 #[aliases("gr", "genesys", "groll")]
 async fn genroll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
@@ -365,8 +826,21 @@ async fn genroll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         },
     };
 
+    // Echo the dice that are actually rolled if upgrades or downgrades changed them
+    let echoed_command = match command_translations::genesys_composition(&in_command) {
+        Ok(composition) if composition != in_command.trim() => format!("{} -> {}", in_command.trim(), composition),
+        _ => in_command.clone(),
+    };
+
+    // Get config data as read-only to look up the emoji, and free it up again before rolling
+    let emoji = {
+        let config_data = ctx.data.read().await;
+        genesys_emoji(config_data.get::<crate::ConfigKey>().expect("Failed to retrieve config!"))
+    };
+    let summary = move |roll: &Roll| genesys_summary(roll, &emoji).unwrap_or_default();
+
     let response = match command_translations::genesys(&in_command) {
-        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &in_command, &roll_command, &roll_comment, false).await {
+        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &echoed_command, &roll_command, &roll_comment, false, Some(&summary)).await {
             Ok(res) => format!("{}", res),
             Err(why) => format!("{}", why),
         },
@@ -377,8 +851,19 @@ async fn genroll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+/// Specialty command for Story Shaper System rolls! ( •̀ ω •́ )✧
+/// 
+/// I'll roll 2d10 and add up whatever modifiers and penalties you give me, e.g.:
+/// > ~s3roll +3
+/// Modifiers can be any math, as long as each one starts with a `+` or `-`.
+/// 
+/// For the variant dice, add `d3` to roll three dice and keep the best two, or `d1` to roll three \$
+/// and keep the worst two:
+/// > ~s3roll d3 +2
+/// 
+/// I'll tell you how it went, too: below 8 is a miss, 8 to 13 a partial success, 14 to 19 a success \$
+/// and 20 or more an exceptional success.
 #[command]
-#[description="This command is for rolling Story Shaper System dice! Help string to be added."]
 #[aliases("s3r")]
 async fn s3roll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
@@ -390,7 +875,7 @@ async fn s3roll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     };
 
     let response = match command_translations::story_shaper(&in_command) {
-        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &roll_command, &roll_command, &roll_comment, true).await {
+        Ok(roll_command) => match new_roll_output(&ctx, &msg, repeat, &roll_command, &roll_command, &roll_comment, true, Some(&story_shaper_outcome)).await {
             Ok(res) => format!("{}", res),
             Err(why) => format!("{}", why),
         },
@@ -401,6 +886,324 @@ async fn s3roll(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+/// Specialty command for D&D 5e d20 rolls! ⚔(ﾟДﾟ)⚔
+/// 
+/// ### Checks, Saves and Attacks
+/// Give me your modifier and I'll roll a d20 and add it, e.g.:
+/// > ~dnd +5
+/// Add `adv` or `dis` for advantage or disadvantage, or `elven` for advantage with three dice \$
+/// (Elven Accuracy). If you have both advantage and disadvantage, they cancel out.
+/// > ~dnd adv +5
+/// 
+/// To check against a DC, add `dc` followed by the number:
+/// > ~dnd dis +3 dc 15
+/// I'll tell you whether you passed, and also call out any natural 20s or 1s.
+/// 
+/// ### Critical Damage
+/// With the `crit` flag, the rest is treated as a damage roll and all the dice in it are rolled twice:
+/// > ~dnd crit 2d6+1d8+3
+/// The modifiers are only added once, as per the rules.
+#[command]
+#[aliases("d20", "5e")]
+async fn dnd(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::dnd(&in_command) {
+        Ok((roll_command, dc)) => {
+            let outcome = move |roll: &Roll| dnd_outcome(roll, dc);
+            match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&outcome)).await {
+                Ok(res) => res,
+                Err(why) => format!("{}", why),
+            }
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Powered by the Apocalypse moves! ٩(ˊᗜˋ*)و
+/// 
+/// Give me your stat (or any other modifiers) and I'll roll 2d6 and add it, e.g.:
+/// > ~pbta +2
+/// I'll tell you whether you got a strong hit (10+), a weak hit (7-9) or a miss (6-).
+/// Add `adv` or `dis` to roll three dice and keep the best or worst two:
+/// > ~pbta adv +1
+/// 
+/// Some games draw the lines elsewhere, so you can move them with `weak` and `strong`:
+/// > ~pbta +1 weak 8 strong 11
+#[command]
+#[aliases("pb", "apoc")]
+async fn pbta(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::pbta(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&pbta_outcome)).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Blades in the Dark and other Forged in the Dark games! (ง •̀_•́)ง
+/// 
+/// Give me the number of dice and I'll roll them, take the highest and tell you how it went, e.g.:
+/// > ~blades 3
+/// Two or more 6s are a critical. If you have no dice, I'll roll two and take the lowest \$
+/// (and you can't crit).
+/// 
+/// For fortune rolls and resistance rolls, add `fortune` or `resist`:
+/// > ~blades 2 fortune
+/// > ~blades 4 resist
+/// Resistance rolls tell you how much stress it costs you (6 minus the highest die).
+#[command]
+#[aliases("bitd", "fitd")]
+async fn blades(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::blades(&in_command) {
+        Ok((roll_command, kind, zero_dice)) => {
+            let outcome = move |roll: &Roll| blades_outcome(roll, kind, zero_dice);
+            match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&outcome)).await {
+                Ok(res) => res,
+                Err(why) => format!("{}", why),
+            }
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Savage Worlds trait rolls! ᕙ(⇀‸↼‶)ᕗ
+/// 
+/// Give me your trait die and any modifiers, and I'll roll it along with a Wild Die and keep the better one, e.g.:
+/// > ~swade d8+1
+/// Both dice ace (explode and add) on their highest face. I'll tell you whether you beat the target number \$
+/// of 4 and how many raises you got, or if you rolled snake eyes.
+/// 
+/// To roll against a different target number, add `tn` followed by the number:
+/// > ~swade d10-2 tn 6
+/// Extras don't get a Wild Die, so add `extra` to roll just the trait die:
+/// > ~swade d6 extra
+#[command]
+#[aliases("savage", "sw")]
+async fn swade(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::swade(&in_command) {
+        Ok((roll_command, tn, wild)) => {
+            let outcome = move |roll: &Roll| swade_outcome(roll, tn, wild);
+            match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&outcome)).await {
+                Ok(res) => res,
+                Err(why) => format!("{}", why),
+            }
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Year Zero Engine games like Mutant: Year Zero, Alien and Forbidden Lands! (ﾉ≧∀≦)ﾉ
+/// 
+/// Give me your base, skill and gear dice, in that order, and I'll roll them all and count 6s as successes, e.g.:
+/// > ~yz 4 2 1
+/// Skill and gear dice can be left out if you don't have any. 1s on base dice are reported as Trauma \$
+/// and 1s on gear dice as Gear Damage.
+/// 
+/// Not happy with the result? Use `push` to reroll everything that isn't a 6 or a 1.
+#[command]
+#[aliases("yearzero", "myz")]
+async fn yz(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::year_zero(&in_command) {
+        Ok(roll_command) => {
+            let (base, skill, _) = command_translations::year_zero_pools(&roll_command).unwrap_or_default();
+            let outcome = move |roll: &Roll| year_zero_outcome(roll, base, skill);
+            match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&outcome)).await {
+                Ok(res) => res,
+                Err(why) => format!("{}", why),
+            }
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Cortex Prime dice pools! ヽ(°〇°)ﾉ
+/// 
+/// Give me your dice and I'll roll them, add up the best two for your total and pick the biggest die left \$
+/// as your effect die, e.g.:
+/// > ~cortex d8 d6 2d10
+/// 1s are hitches, which can't be used for the total or the effect die. If there are no dice left over, \$
+/// the effect die is a d4. If every die is a 1, it's a botch!
+/// 
+/// To pick the biggest effect die first and add up the best two of the rest, add `effect`:
+/// > ~cortex d8 d6 2d10 effect
+#[command]
+#[aliases("cp", "cx")]
+async fn cortex(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::cortex(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Call of Cthulhu 7e skill rolls! ((((；ﾟДﾟ)))
+/// 
+/// Give me your skill value and I'll roll percentile dice against it, e.g.:
+/// > ~coc 55
+/// I'll tell you whether you got a Regular, Hard or Extreme Success, a Critical (01), a Failure, or a Fumble \$
+/// (100, or 96 and up if your skill is below 50).
+/// 
+/// For bonus and penalty dice, add `bonus` or `penalty`, with a number if there's more than one:
+/// > ~coc 55 bonus
+/// > ~coc 40 penalty 2
+/// Each one rolls another tens die, and bonus and penalty dice cancel each other out.
+#[command]
+#[aliases("cthulhu", "cc")]
+async fn coc(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::coc(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, None).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Specialty command for Warhammer Fantasy Roleplay 4e tests! (╬ Ò﹏Ó)
+/// 
+/// Give me the characteristic or skill you're testing and I'll roll d100 under it and count your Success Levels, e.g.:
+/// > ~wfrp 45
+/// Difficulty modifiers go right after it, like `~wfrp 45 +20`. 01-05 always succeeds and 96-00 always fails, \$
+/// and doubles are criticals if you succeed or fumbles if you fail.
+/// 
+/// For an opposed test, add `vs` followed by your opponent's characteristic:
+/// > ~wfrp 45 vs 38
+/// I'll roll for both of you and tell you who wins by how many SL.
+#[command]
+#[aliases("warhammer", "wh")]
+async fn wfrp(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let response = match command_translations::wfrp(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &in_command, &roll_command, &roll_comment, true, Some(&wfrp_outcome)).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
+/// Roll FATE dice and see where you land on the ladder! ᕦ(ò_óˇ)ᕤ
+/// 
+/// Just give me your skill rating (or any other modifiers) and I'll roll 4dF and add it on, e.g.:
+/// > ~fate +3
+/// Each die shows `+`, `-` or a blank, and the total is reported on the ladder, from Terrible (-2) \$
+/// up to Legendary (+8). Modifiers can be any math expression, so `~fate 2+1-1` works too.
+/// 
+/// With no modifier, you just get the bare 4dF:
+/// > ~fate
+#[command]
+#[aliases("fudge", "fr")]
+async fn fate(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (repeat, in_command, roll_comment) = match extract_arguments(ctx, args).await {
+        Ok(arguments) => arguments,
+        Err(why) => {
+            msg.reply_ping(&ctx.http, format!("{}", why)).await?;
+            return Ok(());
+        },
+    };
+
+    let ladder = |roll: &Roll| roll.result().clone().to_decimal().map_or(String::new(), command_translations::fate_ladder);
+    let response = match command_translations::fate(&in_command) {
+        Ok(roll_command) => match new_roll_output(ctx, msg, repeat, &roll_command, &roll_command, &roll_comment, true, Some(&ladder)).await {
+            Ok(res) => res,
+            Err(why) => format!("{}", why),
+        },
+        Err(why) => format!("{}", SixballError::RollError(why)),
+    };
+    msg.reply_ping(&ctx.http, response).await?;
+
+    Ok(())
+}
+
 async fn extract_arguments(ctx: &Context, args: Args) -> Result<(u8, String, String), SixballError> {
     // Get config data as read-only to look up the comment separator. It is then freed up when we move out of the function
     let config_data = ctx.data.read().await;
@@ -418,6 +1221,138 @@ async fn extract_arguments(ctx: &Context, args: Args) -> Result<(u8, String, Str
     Ok((repeat, command, comment))
 }
 
+// Splits an optional ">= N" off the end of a roll string
+fn extract_threshold(message: &str) -> Result<(String, Option<f64>), RollError> {
+    let message = message.to_lowercase().replace('≥', ">=");
+
+    match message.split_once(">=") {
+        Some((roll_command, threshold)) => match threshold.trim().parse::<f64>() {
+            Ok(threshold) => Ok((roll_command.trim().to_owned(), Some(threshold))),
+            Err(_) => Err(RollError::ArgumentError),
+        },
+        None => Ok((message.trim().to_owned(), None)),
+    }
+}
+
+// Calls out a natural 20 or 1 on the d20 that was kept, and checks the total against the DC if there is one
+fn dnd_outcome(roll: &Roll, dc: Option<u16>) -> String {
+    let natural = roll.operations().iter().rev()
+        .filter_map(|operation| operation.clone().pool().ok())
+        .find_map(|pool| pool.dice().iter().find(|die| die.sides == 20).map(|die| die.result));
+
+    let mut outcome = vec![];
+    match natural {
+        Some(20) => outcome.push("Natural 20!".to_string()),
+        Some(1) => outcome.push("Natural 1!".to_string()),
+        _ => {},
+    }
+    if let (Some(dc), Ok(total)) = (dc, roll.result().clone().to_decimal()) {
+        match total >= dc as f64 {
+            true => outcome.push(format!("Success vs DC {}", dc)),
+            false => outcome.push(format!("Failure vs DC {}", dc)),
+        }
+    }
+
+    outcome.join(" ")
+}
+
+fn blades_outcome(roll: &Roll, kind: BladesRoll, zero_dice: bool) -> String {
+    match roll.operations().first().and_then(|operation| operation.clone().pool().ok()) {
+        Some(pool) => {
+            let dice: Vec<u16> = pool.dice().iter().map(|die| die.result).collect();
+            command_translations::blades_outcome(kind, &dice, zero_dice)
+        },
+        None => String::new(),
+    }
+}
+
+fn swade_outcome(roll: &Roll, tn: u16, wild: bool) -> String {
+    // Snake eyes is a 1 on both the trait die and the Wild Die, and 1s never ace, so it shows in both aced pools
+    let aced: Vec<_> = roll.operations().iter()
+        .filter(|operation| matches!(operation, RollToken::Operator(Operator::Explode(_))))
+        .filter_map(|operation| operation.clone().pool().ok())
+        .collect();
+    let snake_eyes = wild && !aced.is_empty() && aced.iter().all(|pool| pool.dice().iter().all(|die| die.result == 1));
+
+    match roll.result().clone().to_decimal() {
+        Ok(total) => command_translations::swade_outcome(total, tn, snake_eyes),
+        Err(_) => String::new(),
+    }
+}
+
+fn year_zero_outcome(roll: &Roll, base: usize, skill: usize) -> String {
+    match roll.operations().last().and_then(|operation| operation.clone().pool().ok()) {
+        Some(pool) => {
+            let dice: Vec<u16> = pool.dice().iter().map(|die| die.result).collect();
+            command_translations::year_zero_outcome(&dice, base, skill)
+        },
+        None => String::new(),
+    }
+}
+
+fn wod_outcome(roll: &Roll) -> String {
+    let botch = roll.operations().iter().any(|operation| matches!(operation, RollToken::Operator(Operator::Target(Target::Botch { botch: true, .. }))));
+
+    match roll.result().clone().to_decimal() {
+        Ok(successes) => command_translations::wod_outcome(successes as i32, botch),
+        Err(_) => String::new(),
+    }
+}
+
+fn wfrp_outcome(roll: &Roll) -> String {
+    let tests: Vec<_> = roll.operations().iter()
+        .filter_map(|operation| match operation {
+            RollToken::Operator(Operator::Target(target)) => target.wfrp_result(),
+            _ => None,
+        })
+        .collect();
+
+    command_translations::wfrp_outcome(&tests)
+}
+
+fn ironsworn_outcome(roll: &Roll) -> String {
+    roll.operations().iter().rev()
+        .find_map(|operation| match operation {
+            RollToken::Operator(Operator::Ironsworn(ironsworn @ Ironsworn { tier: Some(tier), .. })) => match ironsworn.matched() {
+                true => Some(format!("{} with a match", tier)),
+                false => Some(tier.to_string()),
+            },
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn story_shaper_outcome(roll: &Roll) -> String {
+    match roll.result().clone().to_decimal() {
+        Ok(total) => command_translations::story_shaper_outcome(total),
+        Err(_) => String::new(),
+    }
+}
+
+// Symbol names in the config that don't match any symbol are skipped
+fn genesys_emoji(cfg: &Config) -> HashMap<GeneSymbol, String> {
+    cfg.genesys_emoji.iter()
+        .filter_map(|(name, emoji)| name.parse().ok().map(|symbol| (symbol, emoji.clone())))
+        .collect()
+}
+
+fn genesys_summary(roll: &Roll, emoji: &HashMap<GeneSymbol, String>) -> Option<String> {
+    match roll.result() {
+        RollValue::Genesys(gen_val) => Some(gen_val.summary(emoji)),
+        _ => None,
+    }
+}
+
+fn pbta_outcome(roll: &Roll) -> String {
+    roll.operations().iter().rev()
+        .find_map(|operation| match operation {
+            RollToken::Operator(Operator::Pbta(Pbta { tier: Some(tier), .. })) => Some(tier.to_string()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn new_roll_output(
     ctx: &Context,
     msg: &Message,
@@ -426,6 +1361,7 @@ async fn new_roll_output(
     roll_command: &str,
     roll_comment: &str,
     breakdown: bool,
+    label: Option<&(dyn Fn(&Roll) -> String + Send + Sync)>,
 ) -> Result<String, SixballError> {
     // Get config data with write permission to manipulate the tray
     let mut tray_data = ctx.data.write().await;
@@ -457,7 +1393,10 @@ async fn new_roll_output(
             1 => "\n".to_string(),
             _ => format!("\n{}: ", i)
         };
-        let next = roll_format_discord(roll, breakdown, &numbering);
+        let next = match label.map(|label| label(roll)).unwrap_or_default().as_str() {
+            "" => roll_format_discord(roll, breakdown, &numbering),
+            labelled => roll_format_discord(roll, breakdown, &format!("{}{}: ", numbering, labelled)),
+        };
         output.push_str(&next);
     }
 
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::fs;
+use std::{collections::HashMap, fs};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
@@ -10,6 +10,9 @@ pub struct Config {
     pub repeater_separator: String,
     pub log_folder_path: String,
     pub pfp_source: String,
+    // Emoji to show for each Genesys symbol, keyed by symbol name. Symbols without one are written out
+    #[serde(default)]
+    pub genesys_emoji: HashMap<String, String>,
 }
 
 impl Config {
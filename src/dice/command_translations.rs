@@ -1,3 +1,7 @@
+// Reroll everything that isn't a success or a 1, then count successes again
+pub const YEAR_ZERO_PUSH: &str = "ro[2, 3, 4, 5]t6";
+
+use std::{cmp::Ordering, collections::BTreeMap};
 use super::{
     dice_re::*,
     dice_errors::RollError,
@@ -6,13 +10,12 @@ use super::{
 pub fn genesys(in_command: &str) -> Result<String, RollError> {
     let mut out_command = String::new();
 
-    for caps in GENESYS_TOKEN_RE.captures_iter(in_command) {
+    for (kind, number) in genesys_pool(in_command)? {
         if out_command.len() > 0 { out_command.push('&'); }
-        let (kind, number) = (&caps["kind"], &caps["number"]);
         let sides = match kind {
             "b" | "s" => "6",
             "a" | "d" => "8",
-            "p" | "c" => "12",
+            "p" | "c" | "f" => "12",
             other => return Err(RollError::TranslationError(other.into())),
         };
 
@@ -22,6 +25,75 @@ pub fn genesys(in_command: &str) -> Result<String, RollError> {
     Ok(out_command)
 }
 
+// The dice that are actually rolled after upgrades and downgrades, in the same notation as the command
+pub fn genesys_composition(in_command: &str) -> Result<String, RollError> {
+    let composition: Vec<String> = genesys_pool(in_command)?.iter().map(|(kind, number)| format!("{}{}", kind, number)).collect();
+
+    Ok(composition.join(" "))
+}
+
+// Dice are added first, then upgraded, then downgraded, as per Genesys Core Rulebook p. 20
+fn genesys_pool(in_command: &str) -> Result<Vec<(&'static str, u16)>, RollError> {
+    let mut pool: Vec<(&'static str, u16)> = vec![];
+    let mut changes = vec![];
+
+    for caps in GENESYS_TOKEN_RE.captures_iter(in_command) {
+        let number: u16 = caps["number"].parse()?;
+        let kind = match &caps["kind"] {
+            "b" => "b",
+            "s" => "s",
+            "a" => "a",
+            "d" => "d",
+            "p" => "p",
+            "c" => "c",
+            "f" => "f",
+            change => {
+                changes.push((change.to_string(), number));
+                continue;
+            },
+        };
+        match pool.iter_mut().find(|(existing, _)| *existing == kind) {
+            Some((_, count)) => *count += number,
+            None => pool.push((kind, number)),
+        }
+    }
+
+    let mut count = |kind| match pool.iter().position(|(existing, _)| *existing == kind) {
+        Some(index) => index,
+        None => {
+            pool.push((kind, 0));
+            pool.len() - 1
+        },
+    };
+    let (a, p, d, c) = (count("a"), count("p"), count("d"), count("c"));
+
+    // Upgrading with no dice left to upgrade adds a die instead, and downgrading with none left does nothing
+    changes.sort_by_key(|(change, _)| change.starts_with("dg"));
+    for (change, number) in changes {
+        let (lower, upper) = match change.as_str() {
+            "u" | "dg" => (a, p),
+            _ => (d, c),
+        };
+        for _ in 0..number {
+            match (change.starts_with("dg"), pool[lower].1) {
+                (false, 0) => pool[lower].1 += 1,
+                (false, _) => {
+                    pool[lower].1 -= 1;
+                    pool[upper].1 += 1;
+                },
+                (true, _) if pool[upper].1 > 0 => {
+                    pool[upper].1 -= 1;
+                    pool[lower].1 += 1;
+                },
+                (true, _) => (),
+            }
+        }
+    }
+    pool.retain(|&(_, number)| number > 0);
+
+    Ok(pool)
+}
+
 pub fn exalted(in_command: &str) -> Result<String, RollError> {
     let (base, bonus) = in_command.split_once(';').unwrap_or((in_command, ""));
 
@@ -119,19 +191,525 @@ pub fn cofd(in_command: &str) -> Result<String, RollError> {
     Ok(out_command)
 }
 
+pub fn wod(in_command: &str) -> Result<String, RollError> {
+    let (base, bonus) = in_command.split_once(';').unwrap_or((in_command, ""));
+
+    let mut difficulty = 6;
+    let mut specialty = false;
+    for caps in WOD_TOKEN_RE.captures_iter(base) {
+        match caps.name("difficulty") {
+            Some(m) => difficulty = m.as_str().parse()?,
+            None => specialty = true,
+        }
+    }
+    if !(2..=10).contains(&difficulty) {
+        return Err(RollError::TranslationError(format!("difficulty {}", difficulty)));
+    }
+
+    // A specialty needs a success map so that 10s can be worth two
+    let target = match specialty {
+        true => {
+            let mut successes = vec![1; 11 - difficulty];
+            successes[10 - difficulty] += 1;
+            format!("{:?}", successes)
+        },
+        false => difficulty.to_string(),
+    };
+
+    Ok(format!("({})d10t{}b1{}", WOD_TOKEN_RE.replace_all(base, "").trim(), target, bonus))
+}
+
+pub fn wod_outcome(successes: i32, botch: bool) -> String {
+    match (successes, botch) {
+        (_, true) => "Botch!".to_string(),
+        (s, false) if s <= 0 => "Failure".to_string(),
+        (1, false) => "1 success".to_string(),
+        (s, false) => format!("{} successes", s),
+    }
+}
+
+pub fn ironsworn(in_command: &str) -> Result<String, RollError> {
+    let mut progress = None;
+    let mut adds: Option<i32> = None;
+
+    for caps in IRONSWORN_TOKEN_RE.captures_iter(in_command) {
+        if let Some(m) = caps.name("progress") {
+            progress = Some(m.as_str().parse::<u16>()?);
+        } else if let Some(m) = caps.name("modifier") {
+            let modifier: i32 = m.as_str().replace(' ', "").parse()?;
+            adds = Some(adds.unwrap_or(0) + modifier);
+        }
+    }
+
+    // Progress rolls don't use the action die, so there's nothing to add to
+    match (progress, adds) {
+        (Some(_), Some(_)) => Err(RollError::TranslationError(in_command.into())),
+        // The score can only add to the action die, so a net penalty is refused rather than dropped
+        (None, Some(adds)) if adds < 0 => Err(RollError::TranslationError(in_command.into())),
+        (Some(progress), None) => Ok(format!("2d10isp{}", progress)),
+        (None, adds) => Ok(format!("(1d6 & 2d10)is{}", adds.unwrap_or(0))),
+    }
+}
+
+pub fn shadowrun(in_command: &str) -> Result<String, RollError> {
+    let (base, bonus) = in_command.split_once(';').unwrap_or((in_command, ""));
+
+    let mut limit = 0;
+    let mut edge = false;
+    for caps in SR_TOKEN_RE.captures_iter(base) {
+        match caps.name("limit") {
+            Some(m) => limit = m.as_str().parse()?,
+            None => edge = true,
+        }
+    }
+
+    // Pushing the limit with Edge means 6s explode and the limit no longer applies
+    let out_command = match edge {
+        true => format!("({})d6er6sr0{}", SR_TOKEN_RE.replace_all(base, "").trim(), bonus),
+        false => format!("({})d6sr{}{}", SR_TOKEN_RE.replace_all(base, "").trim(), limit, bonus),
+    };
+
+    Ok(out_command)
+}
+
+pub fn l5r(in_command: &str) -> Result<String, RollError> {
+    let mut roll_keep = None;
+    let (mut ring, mut skill) = (0, 0);
+    let (mut emphasis, mut unskilled) = (false, false);
+
+    for caps in L5R_TOKEN_RE.captures_iter(in_command) {
+        if let (Some(rolled), Some(kept)) = (caps.name("rolled"), caps.name("kept")) {
+            roll_keep = Some((rolled.as_str().parse::<u16>()?, kept.as_str().parse::<u16>()?));
+        } else if let Some(m) = caps.name("ring") {
+            ring += m.as_str().parse::<u16>()?;
+        } else if let Some(m) = caps.name("skill") {
+            skill += m.as_str().parse::<u16>()?;
+        } else {
+            match &caps[0] {
+                "e" => emphasis = true,
+                _ => unskilled = true,
+            }
+        }
+    }
+
+    match roll_keep {
+        // 4e roll and keep: anything that isn't a flag is a modifier to the total
+        Some((rolled, kept)) => {
+            let (rolled, kept, bonus) = ten_dice_rule(rolled, kept);
+            let modifier = L5R_TOKEN_RE.replace_all(in_command, "").replace(' ', "");
+            let base = format!(
+                "{}d10{}{}k{}{}",
+                rolled,
+                if emphasis { "ro1" } else { "" },
+                if unskilled { "" } else { "ea10" },
+                kept,
+                if bonus > 0 { format!("+{}", bonus) } else { String::new() }
+            );
+            Ok(match modifier.as_str() {
+                "" => base,
+                m if m.starts_with(['+', '-']) => format!("{}{}", base, m),
+                m => format!("{}+{}", base, m),
+            })
+        },
+        // 5e narrative dice: explosive successes get to roll another die of the same kind
+        None => {
+            let mut out_command = String::new();
+            if ring > 0 { out_command.push_str(&format!("{}d6er6lr", ring)); }
+            if skill > 0 {
+                if !out_command.is_empty() { out_command.push('&'); }
+                out_command.push_str(&format!("{}d12er[11, 12]ls", skill));
+            }
+            match out_command.is_empty() {
+                true => Err(RollError::TranslationError(in_command.into())),
+                false => Ok(out_command),
+            }
+        },
+    }
+}
+
+// L5R 4e's Ten Dice Rule: every two rolled dice past 10 become a kept die, and every kept die past 10 becomes +2
+fn ten_dice_rule(rolled: u16, kept: u16) -> (u16, u16, u16) {
+    let (mut rolled, mut kept) = (rolled, kept.min(rolled));
+    if rolled > 10 {
+        kept += (rolled - 10) / 2;
+        rolled = 10;
+    }
+    let bonus = kept.saturating_sub(10) * 2;
+
+    (rolled, kept.min(10), bonus)
+}
+
+// Gives back the roll and the DC to check it against, if any
+pub fn dnd(in_command: &str) -> Result<(String, Option<u16>), RollError> {
+    let (mut advantage, mut disadvantage, mut elven, mut crit) = (false, false, false, false);
+    let mut dc = None;
+
+    for caps in DND_TOKEN_RE.captures_iter(in_command) {
+        match caps.name("dc") {
+            Some(m) => dc = Some(m.as_str().parse()?),
+            None => match &caps[0] {
+                "elven" => elven = true,
+                "crit" => crit = true,
+                flag if flag.starts_with("adv") => advantage = true,
+                _ => disadvantage = true,
+            },
+        }
+    }
+    let modifier = DND_TOKEN_RE.replace_all(in_command, "").replace(' ', "");
+
+    // Critical hits roll all the damage dice twice, but modifiers only once
+    if crit {
+        return Ok((DND_DICE_RE.replace_all(&modifier, "${0}crit").into_owned(), None));
+    }
+
+    // Advantage and disadvantage cancel out no matter how many sources of either there are
+    let base = match (advantage || elven, disadvantage) {
+        (true, false) if elven => "3d20kh1",
+        (true, false) => "2d20kh1",
+        (false, true) => "2d20kl1",
+        _ => "1d20",
+    };
+    let out_command = match modifier.as_str() {
+        "" => base.to_string(),
+        m if m.starts_with(['+', '-']) => format!("{}{}", base, m),
+        m => format!("{}+{}", base, m),
+    };
+
+    Ok((out_command, dc))
+}
+
+pub fn pbta(in_command: &str) -> Result<String, RollError> {
+    let (mut advantage, mut disadvantage) = (false, false);
+    let (mut weak, mut strong) = (7, 10);
+
+    for caps in PBTA_TOKEN_RE.captures_iter(in_command) {
+        if let Some(m) = caps.name("weak") {
+            weak = m.as_str().parse()?;
+        } else if let Some(m) = caps.name("strong") {
+            strong = m.as_str().parse()?;
+        } else if caps[0].starts_with("adv") {
+            advantage = true;
+        } else {
+            disadvantage = true;
+        }
+    }
+    if weak > strong {
+        return Err(RollError::TranslationError(format!("weak {} strong {}", weak, strong)));
+    }
+    let modifier = PBTA_TOKEN_RE.replace_all(in_command, "").replace(' ', "");
+
+    let base = match (advantage, disadvantage) {
+        (true, false) => "3d6kh2",
+        (false, true) => "3d6kl2",
+        _ => "2d6",
+    };
+    let total = match modifier.as_str() {
+        "" => base.to_string(),
+        m if m.starts_with(['+', '-']) => format!("{}{}", base, m),
+        m => format!("{}+{}", base, m),
+    };
+
+    Ok(format!("({})pbta[{}, {}]", total, weak, strong))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BladesRoll {
+    Action,
+    Fortune,
+    Resistance,
+}
+
+pub fn blades(in_command: &str) -> Result<(String, BladesRoll, bool), RollError> {
+    let mut kind = BladesRoll::Action;
+    for caps in BLADES_TOKEN_RE.captures_iter(in_command) {
+        match &caps[0] {
+            "fortune" => kind = BladesRoll::Fortune,
+            _ => kind = BladesRoll::Resistance,
+        }
+    }
+    let pool = BLADES_TOKEN_RE.replace_all(in_command, "").replace(' ', "");
+    let dice: u16 = match pool.as_str() {
+        "" => return Err(RollError::TranslationError("no dice".into())),
+        number => number.parse()?,
+    };
+
+    // With no dice, roll two and take the worse one
+    let out_command = match dice {
+        0 => "2d6kl1".to_string(),
+        number => format!("{}d6kh1", number),
+    };
+
+    Ok((out_command, kind, dice == 0))
+}
+
+// Reads the whole pool rather than just the kept die, since criticals need to see every 6
+pub fn blades_outcome(kind: BladesRoll, dice: &[u16], zero_dice: bool) -> String {
+    let critical = !zero_dice && dice.iter().filter(|&&die| die == 6).count() > 1;
+    let result = match zero_dice {
+        true => dice.iter().min(),
+        false => dice.iter().max(),
+    }.copied().unwrap_or(1);
+
+    let outcome = match (kind, critical, result) {
+        (BladesRoll::Action, true, _) => "Critical success!",
+        (BladesRoll::Action, false, 6) => "Full success",
+        (BladesRoll::Action, false, 4..=5) => "Partial success",
+        (BladesRoll::Action, false, _) => "Bad outcome",
+        (BladesRoll::Fortune, true, _) => "Critical: exceptional result!",
+        (BladesRoll::Fortune, false, 6) => "Good result",
+        (BladesRoll::Fortune, false, 4..=5) => "Mixed result",
+        (BladesRoll::Fortune, false, _) => "Bad result",
+        (BladesRoll::Resistance, true, _) => "Critical! Clear 1 stress",
+        (BladesRoll::Resistance, false, _) => return format!("Stress cost: {}", 6 - result.min(6)),
+    };
+
+    outcome.to_string()
+}
+
+pub fn swade(in_command: &str) -> Result<(String, u16, bool), RollError> {
+    let mut sides = None;
+    let mut tn = 4;
+    let mut wild = true;
+
+    for caps in SWADE_TOKEN_RE.captures_iter(in_command) {
+        if let Some(m) = caps.name("sides") {
+            sides = Some(m.as_str().parse::<u16>()?);
+        } else if let Some(m) = caps.name("tn") {
+            tn = m.as_str().parse()?;
+        } else {
+            wild = false;
+        }
+    }
+    let sides = sides.ok_or_else(|| RollError::TranslationError(in_command.into()))?;
+    let modifier = SWADE_TOKEN_RE.replace_all(in_command, "").replace(' ', "");
+
+    // Both dice ace, and Wild Cards take the better of the trait die and the Wild Die
+    let trait_die = format!("1d{}ea{}", sides, sides);
+    let base = match wild {
+        true => format!("({} & 1d6ea6)kh1", trait_die),
+        false => trait_die,
+    };
+    let out_command = match modifier.as_str() {
+        "" => base,
+        m if m.starts_with(['+', '-']) => format!("{}{}", base, m),
+        m => format!("{}+{}", base, m),
+    };
+
+    Ok((out_command, tn, wild))
+}
+
+pub fn swade_outcome(total: f64, tn: u16, snake_eyes: bool) -> String {
+    if snake_eyes { return "Critical failure!".into(); }
+    if total < tn as f64 { return "Failure".into(); }
+
+    // Every full 4 points over the target number is a raise
+    match ((total - tn as f64) / 4.0).floor() as u32 {
+        0 => "Success".into(),
+        1 => "Success with a raise".into(),
+        raises => format!("Success with {} raises", raises),
+    }
+}
+
+pub fn year_zero(in_command: &str) -> Result<String, RollError> {
+    let pools = in_command.split_whitespace().map(|number| number.parse()).collect::<Result<Vec<u16>, _>>()?;
+    let (base, skill, gear) = match pools[..] {
+        [base] => (base, 0, 0),
+        [base, skill] => (base, skill, 0),
+        [base, skill, gear] => (base, skill, gear),
+        _ => return Err(RollError::TranslationError(in_command.into())),
+    };
+
+    // The pools are merged in a fixed order, so the outcome can still tell them apart after a push
+    Ok(format!("({}d6 & {}d6 & {}d6)t6", base, skill, gear))
+}
+
+pub fn year_zero_pools(roll_command: &str) -> Option<(usize, usize, usize)> {
+    let caps = YEAR_ZERO_ROLL_RE.captures(roll_command)?;
+
+    Some((caps["base"].parse().ok()?, caps["skill"].parse().ok()?, caps["gear"].parse().ok()?))
+}
+
+pub fn year_zero_outcome(dice: &[u16], base: usize, skill: usize) -> String {
+    let ones = |pool: &[u16]| pool.iter().filter(|&&die| die == 1).count();
+    let trauma = ones(dice.get(..base).unwrap_or_default());
+    let breakage = ones(dice.get((base + skill)..).unwrap_or_default());
+
+    let mut outcome = vec![];
+    if trauma > 0 { outcome.push(format!("{} Trauma", trauma)); }
+    if breakage > 0 { outcome.push(format!("{} Gear Damage", breakage)); }
+
+    outcome.join(", ")
+}
+
+pub fn cortex(in_command: &str) -> Result<String, RollError> {
+    let mut dice = BTreeMap::new();
+    let mut pick = "ct";
+
+    for caps in CORTEX_TOKEN_RE.captures_iter(in_command) {
+        match caps.name("sides") {
+            Some(sides) => {
+                let number = match &caps["number"] {
+                    "" => 1,
+                    number => number.parse()?,
+                };
+                *dice.entry(sides.as_str().parse::<u16>()?).or_insert(0) += number;
+            },
+            None => pick = "ce",
+        }
+    }
+    if dice.is_empty() {
+        return Err(RollError::TranslationError(in_command.into()));
+    }
+
+    let (sides, numbers): (Vec<u16>, Vec<u16>) = dice.into_iter().unzip();
+
+    Ok(format!("{:?}d{:?}{}2", numbers, sides, pick))
+}
+
+pub fn coc(in_command: &str) -> Result<String, RollError> {
+    let mut skill = None;
+    let mut extra_dice: i32 = 0;
+
+    for caps in COC_TOKEN_RE.captures_iter(in_command) {
+        let count = |m: regex::Match| match m.as_str() {
+            "" => Ok(1),
+            number => number.parse::<i32>(),
+        };
+        if let Some(m) = caps.name("bonus") {
+            extra_dice += count(m)?;
+        } else if let Some(m) = caps.name("penalty") {
+            extra_dice -= count(m)?;
+        } else {
+            skill = Some(caps["skill"].parse::<u16>()?);
+        }
+    }
+    let skill = skill.ok_or_else(|| RollError::TranslationError(in_command.into()))?;
+
+    // Bonus and penalty dice cancel each other out one for one, and whatever's left is rolled as extra tens dice
+    let operator = match extra_dice < 0 {
+        true => "cocp",
+        false => "coc",
+    };
+
+    Ok(format!("{}dp{}{}", 1 + extra_dice.unsigned_abs(), operator, skill))
+}
+
+pub fn wfrp(in_command: &str) -> Result<String, RollError> {
+    let mut targets: Vec<i32> = vec![];
+
+    for caps in WFRP_TOKEN_RE.captures_iter(in_command) {
+        if let Some(m) = caps.name("modifier") {
+            let modifier: i32 = m.as_str().replace(' ', "").parse()?;
+            match targets.last_mut() {
+                Some(target) => *target += modifier,
+                None => return Err(RollError::TranslationError(m.as_str().into())),
+            }
+        } else if let Some(m) = caps.name("opposed").or_else(|| caps.name("characteristic")) {
+            targets.push(m.as_str().parse()?);
+        }
+    }
+    if targets.is_empty() || targets.len() > 2 {
+        return Err(RollError::TranslationError(in_command.into()));
+    }
+
+    // Opposed tests subtract the opponent's Success Levels from the tester's
+    let tests: Vec<String> = targets.iter().map(|target| format!("1dpwf{}", target.max(&0))).collect();
+
+    Ok(tests.join(" - "))
+}
+
+// Takes the characteristic, roll, Success Levels and whether it passed for each test, with the opponent's test second
+pub fn wfrp_outcome(tests: &[(u16, u16, i32, bool)]) -> String {
+    let describe = |&(characteristic, roll, _, passed): &(u16, u16, i32, bool)| {
+        let double = roll % 100 / 10 == roll % 10;
+        match (passed, double) {
+            (true, true) => "Critical success!",
+            (false, true) => "Fumble!",
+            (true, false) if roll > characteristic => "Automatic success",
+            (false, false) if roll <= characteristic => "Automatic failure",
+            (true, false) => "Success",
+            (false, false) => "Failure",
+        }
+    };
+
+    match tests {
+        [test] => describe(test).to_string(),
+        [test, opposed] => {
+            let verdict = match test.2.cmp(&opposed.2) {
+                Ordering::Greater => format!("you win by {} SL", test.2 - opposed.2),
+                Ordering::Less => format!("opponent wins by {} SL", opposed.2 - test.2),
+                // Ties on Success Levels go to the higher characteristic
+                Ordering::Equal => match test.0.cmp(&opposed.0) {
+                    Ordering::Greater => "you win on higher characteristic".to_string(),
+                    Ordering::Less => "opponent wins on higher characteristic".to_string(),
+                    Ordering::Equal => "stalemate".to_string(),
+                },
+            };
+            format!("{} vs {}, {}", describe(test), describe(opposed), verdict)
+        },
+        _ => String::new(),
+    }
+}
+
 pub fn story_shaper(in_command: &str) -> Result<String, RollError> {
-    let mut out_command = String::from("2d10");
+    let mut dice = "2d10";
+    let mut modifiers = String::new();
     for caps in S3_TOKEN_RE.captures_iter(in_command) {
-        let modifier = match caps.name("mod") {
-            Some(m) => m.as_str(),
-            None => "",
-        };
-        out_command.push_str(modifier);
+        if let Some(m) = caps.name("mod") {
+            modifiers.push_str(m.as_str());
+        } else if let Some(m) = caps.name("dice") {
+            // Three dice keep the best two and one die keeps the worst two of three, so the total always comes from two dice
+            dice = match m.as_str().ends_with('3') {
+                true => "3d10kh2",
+                false => "3d10kl2",
+            };
+        }
     }
 
+    Ok(format!("{}{}", dice, modifiers))
+}
+
+pub fn story_shaper_outcome(total: f64) -> String {
+    match total.round() as i64 {
+        t if t >= 20 => "Exceptional success",
+        t if t >= 14 => "Success",
+        t if t >= 8 => "Partial success",
+        _ => "Miss",
+    }.to_string()
+}
+
+pub fn fate(in_command: &str) -> Result<String, RollError> {
+    // Skill ratings and other modifiers just get added on, with or without a sign in front
+    let out_command = match in_command.trim() {
+        "" => "4df".to_string(),
+        modifier if modifier.starts_with(['+', '-']) => format!("4df{}", modifier),
+        modifier => format!("4df+{}", modifier),
+    };
+
     Ok(out_command)
 }
 
+// Where a total lands on the FATE adjective ladder. Anything past either end is described relative to that end
+pub fn fate_ladder(total: f64) -> String {
+    let rung = match total.round() as i64 {
+        t if t > 8 => "Beyond Legendary",
+        8 => "Legendary",
+        7 => "Epic",
+        6 => "Fantastic",
+        5 => "Superb",
+        4 => "Great",
+        3 => "Good",
+        2 => "Fair",
+        1 => "Average",
+        0 => "Mediocre",
+        -1 => "Poor",
+        -2 => "Terrible",
+        _ => "Beyond Terrible",
+    };
+
+    rung.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
@@ -144,6 +722,12 @@ mod tests {
         let out_command = genesys(in_command).unwrap();
 
         assert_eq!(out_command, "2d8ga&2d12gp&2d6gb&2d8gd&2d12gc&2d6gs".to_owned());
+        assert_eq!(genesys("a2 f1").unwrap(), "2d8ga&1d12gf");
+        assert_eq!(genesys("a2 d2 u1 ud1").unwrap(), "1d8ga&1d8gd&1d12gp&1d12gc");
+        assert_eq!(genesys("p1 u2 dg1").unwrap(), "1d12gp&1d8ga");
+        assert_eq!(genesys("c1 dgd2 s1").unwrap(), "1d6gs&1d8gd");
+        assert_eq!(genesys("a1 a1 ud1").unwrap(), "2d8ga&1d8gd");
+        assert_eq!(genesys_composition("a3 d2 u1 b1").unwrap(), "a2 d2 b1 p1");
     }
 
     #[test]
@@ -198,11 +782,268 @@ mod tests {
 
     #[test]
     fn test_3s() {
-        let in_commands: Vec<&str> = vec!["", "+5", "-(2+3)*4/5"];
-        let out_commands: Vec<&str> = vec!["2d10", "2d10+5", "2d10-(2+3)*4/5"];
+        let in_commands: Vec<&str> = vec!["", "+5", "-(2+3)*4/5", "d3 +2", "d1", "d 3 -1"];
+        let out_commands: Vec<&str> = vec!["2d10", "2d10+5", "2d10-(2+3)*4/5", "3d10kh2+2", "3d10kl2", "3d10kh2-1"];
 
         for i in 0..in_commands.len() {
             assert_eq!(out_commands[i], story_shaper(in_commands[i]).unwrap());
         }
+
+        assert_eq!(story_shaper_outcome(7.0), "Miss");
+        assert_eq!(story_shaper_outcome(8.0), "Partial success");
+        assert_eq!(story_shaper_outcome(14.0), "Success");
+        assert_eq!(story_shaper_outcome(23.0), "Exceptional success");
+    }
+
+    #[test]
+    fn test_fate() {
+        let in_commands = ["", "3", "+2", "-1", "2+1"];
+        let out_commands = ["4df", "4df+3", "4df+2", "4df-1", "4df+2+1"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], fate(in_commands[i]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_fate_ladder() {
+        assert_eq!(fate_ladder(3.0), "Good");
+        assert_eq!(fate_ladder(0.0), "Mediocre");
+        assert_eq!(fate_ladder(-2.0), "Terrible");
+        assert_eq!(fate_ladder(12.0), "Beyond Legendary");
+        assert_eq!(fate_ladder(-4.0), "Beyond Terrible");
+        assert_eq!(fate_ladder(8.0), "Legendary");
+        assert_eq!(fate_ladder(9.0), "Beyond Legendary");
+        assert_eq!(fate_ladder(-1.0), "Poor");
+        assert_eq!(fate_ladder(-3.0), "Beyond Terrible");
+    }
+
+    #[test]
+    fn test_shadowrun() {
+        let in_commands = ["12", "8+4", "12 l5", "12 e", "12 l5 e", "12 l4; +1"];
+        let out_commands = ["(12)d6sr0", "(8+4)d6sr0", "(12)d6sr5", "(12)d6er6sr0", "(12)d6er6sr0", "(12)d6sr4 +1"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], shadowrun(in_commands[i]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_l5r() {
+        let in_commands = ["6k3", "6 k 3 + 5", "6k3 5", "6k3e", "6k3u", "12k4", "14k9", "3k5", "r3 s2", "r2", "s1 s1"];
+        let out_commands = [
+            "6d10ea10k3",
+            "6d10ea10k3+5",
+            "6d10ea10k3+5",
+            "6d10ro1ea10k3",
+            "6d10k3",
+            "10d10ea10k5",
+            "10d10ea10k10+2",
+            "3d10ea10k3",
+            "3d6er6lr&2d12er[11, 12]ls",
+            "2d6er6lr",
+            "2d12er[11, 12]ls",
+        ];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], l5r(in_commands[i]).unwrap());
+        }
+        assert!(l5r("").is_err());
+    }
+
+    #[test]
+    fn test_dnd() {
+        let in_commands = ["", "+5", "5 dc 15", "adv +3", "dis-1 dc12", "elven +7", "adv dis +2", "elven dis", "adv adv dis +1", "crit 2d6+1d8+3", "crit 1d8 + 4", "crit adv 1d6-1"];
+        let out_commands = [
+            ("1d20", None),
+            ("1d20+5", None),
+            ("1d20+5", Some(15)),
+            ("2d20kh1+3", None),
+            ("2d20kl1-1", Some(12)),
+            ("3d20kh1+7", None),
+            ("1d20+2", None),
+            ("1d20", None),
+            ("1d20+1", None),
+            ("2d6crit+1d8crit+3", None),
+            ("1d8crit+4", None),
+            ("1d6crit-1", None),
+        ];
+
+        for i in 0..in_commands.len() {
+            assert_eq!((out_commands[i].0.to_string(), out_commands[i].1), dnd(in_commands[i]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_pbta() {
+        let in_commands = ["", "+2", "-1", "adv +1", "dis 2", "adv dis +3", "+1 weak 8 strong 11", "strong12"];
+        let out_commands = [
+            "(2d6)pbta[7, 10]",
+            "(2d6+2)pbta[7, 10]",
+            "(2d6-1)pbta[7, 10]",
+            "(3d6kh2+1)pbta[7, 10]",
+            "(3d6kl2+2)pbta[7, 10]",
+            "(2d6+3)pbta[7, 10]",
+            "(2d6+1)pbta[8, 11]",
+            "(2d6)pbta[7, 12]",
+        ];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], pbta(in_commands[i]).unwrap());
+        }
+        assert!(pbta("weak 11 strong 9").is_err());
+    }
+
+    #[test]
+    fn test_blades() {
+        let in_commands = ["3", "1 fortune", "0", "4 resist", "0 resistance", "300"];
+        let out_commands = [
+            ("3d6kh1", BladesRoll::Action, false),
+            ("1d6kh1", BladesRoll::Fortune, false),
+            ("2d6kl1", BladesRoll::Action, true),
+            ("4d6kh1", BladesRoll::Resistance, false),
+            ("2d6kl1", BladesRoll::Resistance, true),
+            ("300d6kh1", BladesRoll::Action, false),
+        ];
+
+        for i in 0..in_commands.len() {
+            let (command, kind, zero_dice) = blades(in_commands[i]).unwrap();
+            assert_eq!((out_commands[i].0.to_string(), out_commands[i].1, out_commands[i].2), (command, kind, zero_dice));
+        }
+        assert!(blades("fortune").is_err());
+    }
+
+    #[test]
+    fn test_blades_outcome() {
+        assert_eq!(blades_outcome(BladesRoll::Action, &[6, 2, 6], false), "Critical success!");
+        assert_eq!(blades_outcome(BladesRoll::Action, &[6, 2, 3], false), "Full success");
+        assert_eq!(blades_outcome(BladesRoll::Action, &[5, 4], false), "Partial success");
+        assert_eq!(blades_outcome(BladesRoll::Action, &[6, 6], true), "Full success");
+        assert_eq!(blades_outcome(BladesRoll::Action, &[6, 3], true), "Bad outcome");
+        assert_eq!(blades_outcome(BladesRoll::Fortune, &[4], false), "Mixed result");
+        assert_eq!(blades_outcome(BladesRoll::Resistance, &[2, 5], false), "Stress cost: 1");
+        assert_eq!(blades_outcome(BladesRoll::Resistance, &[2, 5], true), "Stress cost: 4");
+        assert_eq!(blades_outcome(BladesRoll::Resistance, &[6, 6, 1], false), "Critical! Clear 1 stress");
+    }
+
+    #[test]
+    fn test_swade() {
+        let in_commands = ["d8", "d8+1", "d10 -2 tn 6", "d6 extra +1", "d12+1+2"];
+        let out_commands = [
+            ("(1d8ea8 & 1d6ea6)kh1", 4, true),
+            ("(1d8ea8 & 1d6ea6)kh1+1", 4, true),
+            ("(1d10ea10 & 1d6ea6)kh1-2", 6, true),
+            ("1d6ea6+1", 4, false),
+            ("(1d12ea12 & 1d6ea6)kh1+1+2", 4, true),
+        ];
+
+        for i in 0..in_commands.len() {
+            let (command, tn, wild) = swade(in_commands[i]).unwrap();
+            assert_eq!((out_commands[i].0.to_string(), out_commands[i].1, out_commands[i].2), (command, tn, wild));
+        }
+        assert!(swade("+2").is_err());
+    }
+
+    #[test]
+    fn test_swade_outcome() {
+        assert_eq!(swade_outcome(3.0, 4, false), "Failure");
+        assert_eq!(swade_outcome(7.0, 4, false), "Success");
+        assert_eq!(swade_outcome(8.0, 4, false), "Success with a raise");
+        assert_eq!(swade_outcome(4.0, 4, false), "Success");
+        assert_eq!(swade_outcome(11.0, 4, false), "Success with a raise");
+        assert_eq!(swade_outcome(12.0, 4, false), "Success with 2 raises");
+        assert_eq!(swade_outcome(13.0, 4, false), "Success with 2 raises");
+        assert_eq!(swade_outcome(22.0, 6, false), "Success with 4 raises");
+        assert_eq!(swade_outcome(3.0, 2, true), "Critical failure!");
+        assert_eq!(swade_outcome(2.0, 4, true), "Critical failure!");
+    }
+
+    #[test]
+    fn test_year_zero() {
+        let in_commands = ["5", "3 2", "4 2 1", "2 0 3"];
+        let out_commands = ["(5d6 & 0d6 & 0d6)t6", "(3d6 & 2d6 & 0d6)t6", "(4d6 & 2d6 & 1d6)t6", "(2d6 & 0d6 & 3d6)t6"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], year_zero(in_commands[i]).unwrap());
+            assert!(year_zero_pools(out_commands[i]).is_some());
+        }
+        assert!(year_zero("").is_err());
+        assert!(year_zero("1 2 3 4").is_err());
+        assert_eq!(year_zero_pools("(4d6 & 2d6 & 1d6)t6ro[2, 3, 4, 5]t6"), Some((4, 2, 1)));
+        assert_eq!(year_zero_pools("4d6t6"), None);
+
+        assert_eq!(year_zero_outcome(&[1, 6, 1, 1, 3, 1], 3, 2), "2 Trauma, 1 Gear Damage");
+        assert_eq!(year_zero_outcome(&[6, 2, 1], 2, 1), "");
+    }
+
+    #[test]
+    fn test_cortex() {
+        let in_commands = ["d8 d6 d10", "2d8 d6 d8", "d10 d4 effect", "3d6"];
+        let out_commands = ["[1, 1, 1]d[6, 8, 10]ct2", "[1, 3]d[6, 8]ct2", "[1, 1]d[4, 10]ce2", "[3]d[6]ct2"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], cortex(in_commands[i]).unwrap());
+        }
+        assert!(cortex("effect").is_err());
+    }
+
+    #[test]
+    fn test_coc() {
+        let in_commands = ["50", "65 bonus", "40 penalty 2", "55 bonus 2 penalty", "30 bonus penalty"];
+        let out_commands = ["1dpcoc50", "2dpcoc65", "3dpcocp40", "2dpcoc55", "1dpcoc30"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], coc(in_commands[i]).unwrap());
+        }
+        assert!(coc("bonus").is_err());
+    }
+
+    #[test]
+    fn test_wfrp() {
+        let in_commands = ["45", "45 +20", "45 -10 vs 38", "45 vs 38 +10", "10 -30"];
+        let out_commands = ["1dpwf45", "1dpwf65", "1dpwf35 - 1dpwf38", "1dpwf45 - 1dpwf48", "1dpwf0"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], wfrp(in_commands[i]).unwrap());
+        }
+        assert!(wfrp("+20").is_err());
+        assert!(wfrp("45 vs 38 vs 30").is_err());
+
+        assert_eq!(wfrp_outcome(&[(45, 34, 1, true)]), "Success");
+        assert_eq!(wfrp_outcome(&[(45, 44, 0, true)]), "Critical success!");
+        assert_eq!(wfrp_outcome(&[(2, 4, 0, true)]), "Automatic success");
+        assert_eq!(wfrp_outcome(&[(99, 97, 0, false)]), "Automatic failure");
+        assert_eq!(wfrp_outcome(&[(45, 34, 1, true), (38, 60, -3, false)]), "Success vs Failure, you win by 4 SL");
+        assert_eq!(wfrp_outcome(&[(40, 34, 1, true), (45, 34, 1, true)]), "Success vs Success, opponent wins on higher characteristic");
+        assert_eq!(wfrp_outcome(&[(45, 34, 1, true), (40, 34, 1, true)]), "Success vs Success, you win on higher characteristic");
+        assert_eq!(wfrp_outcome(&[(40, 34, 1, true), (40, 34, 1, true)]), "Success vs Success, stalemate");
+    }
+
+    #[test]
+    fn test_wod() {
+        let in_commands = ["5", "5+2 diff 7", "5 d8 spec", "6 spec", "4 difficulty 10 s", "5; +1"];
+        let out_commands = ["(5)d10t6b1", "(5+2)d10t7b1", "(5)d10t[1, 1, 2]b1", "(6)d10t[1, 1, 1, 1, 2]b1", "(4)d10t[2]b1", "(5)d10t6b1 +1"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], wod(in_commands[i]).unwrap());
+        }
+        assert!(wod("5 diff 11").is_err());
+
+        assert_eq!(wod_outcome(2, false), "2 successes");
+        assert_eq!(wod_outcome(-1, false), "Failure");
+        assert_eq!(wod_outcome(-1, true), "Botch!");
+    }
+
+    #[test]
+    fn test_ironsworn() {
+        let in_commands = ["+2", "2 +1", "3 -1 +2", "1 -1", "", "progress 7", "progress 12"];
+        let out_commands = ["(1d6 & 2d10)is2", "(1d6 & 2d10)is3", "(1d6 & 2d10)is4", "(1d6 & 2d10)is0", "(1d6 & 2d10)is0", "2d10isp7", "2d10isp12"];
+
+        for i in 0..in_commands.len() {
+            assert_eq!(out_commands[i], ironsworn(in_commands[i]).unwrap());
+        }
+        assert!(ironsworn("progress 7 +2").is_err());
+        assert!(ironsworn("-1").is_err());
+        assert!(ironsworn("1 -3").is_err());
     }
 }
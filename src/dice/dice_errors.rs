@@ -13,11 +13,13 @@ pub enum RollError {
     ArgumentError,
     BlockedExplosionError,
     FBomb,
+    IntractableError,
     MathError(MathError),
     MissingPoolError,
     NotANumberError,
     NotImplementedError,
     NotResolvedError,
+    OverflowError,
     PlaceholderError,
     ParseError(num::ParseIntError),
     RetrieveError,
@@ -33,11 +35,13 @@ impl fmt::Display for RollError {
             RollError::ArgumentError => write!(f, "ぇ━(*´･д･)━!!! I don't know what to do with this! (Failed to find an argument or wrong argument)"),
             RollError::BlockedExplosionError => write!(f, "Okay, let's slow down here... (｡･_･｡)ﾉ ﾁｮｲﾏﾁ｡ That's too explosive for my tastes! (Be nice and don't try to go infinite)"),
             RollError::FBomb => write!(f, "What the fuck"),
+            RollError::IntractableError => write!(f, "That's way too many possibilities to keep track of! (＠_＠;) (Can't work out the exact odds of this roll)"),
             RollError::MathError(why) => write!(f, "{}", why),
             RollError::MissingPoolError => write!(f, "Where'd the dice go!? !!!∑(ﾟﾛﾟ!(ﾟﾍﾟ?)??? I swear I was looking... (Failed to find a dicepool)"),
             RollError::NotANumberError => write!(f, "Hold up, that's not a number! ヾ(｡｀Д´｡)ﾉ彡☆ﾌﾞｰﾌﾞｰｯ!! (Tried to treat a non-numerical value as a number)"),
             RollError::NotImplementedError => write!(f, "I'm sorry, I can't actually do that yet... (m´・ω・｀)m ｺﾞﾒﾝ…"),
             RollError::NotResolvedError => write!(f, "Hooold up! (｡･_･｡)ﾉ ﾁｮｲﾏﾁ｡ Something's happening out of order here?? (Tried to use an operator before resolving it)"),
+            RollError::OverflowError => write!(f, "Whoa whoa whoa, that's way too big! ＼(◎o◎)／ (A number or dicepool went over the limit of 65535)"),
             RollError::PlaceholderError => write!(f, "Error handling TBA"),
             RollError::ParseError(why) => write!(f, "((((；´ﾟДﾟ))) These dice are too spicy for me! ({})", why),
             RollError::RetrieveError => write!(f, "Sorry, I lost your dice (m´・ω・｀)m ｺﾞﾒﾝ… (Attempted to retrieve roll from empty tray)"),
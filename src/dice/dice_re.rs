@@ -3,25 +3,59 @@ use regex::Regex;
 use crate::math::math_re::MATH_TOKEN_STRING;
 
 const DICE_TOKEN_STRING: &str = r"
+    \{\d+(?:,\d+)*\}d\d+ # Literal pool, {results}d{sides}
+    |
+    \{d\d+:\d+(?:,\d+)*\} # Literal pool, {d{sides}: results}
+    |
+    d[hl]               # Drop, before dice notation so 4d6dl1 isn't read as dice
+    |
     d                   # Dice notation
     |
+    f                   # Fudge dice, as in 4df
+    |
     [tb]                # Target number or botch number
     |
+    wf                  # WFRP Success Levels
+    |
     k[elh]?             # Keep
     |
+    pbta                # Powered by the Apocalypse outcome tiers
+    |
+    p                   # Percentile dice, as in 1dp
+    |
+    sr                  # Shadowrun hits
+    |
+    is[pm]?             # Ironsworn action, progress and momentum scores
+    |
+    ore                 # One-Roll Engine sets
+    |
     r[obwr]?            # Reroll
     |
     e[aor]?             # Explode
     |
+    crit                # Critical hit, roll the dice twice
+    |
+    cocp?               # Call of Cthulhu success levels, with penalty dice
+    |
+    c[te]               # Cortex total and effect die
+    |
+    c(?:max|min|[hl])?  # Cap
+    |
     &                   # Merge
     |
-    g[bsadpc]           # Genesys dice
+    g[bsadpcf]          # Genesys dice
+    |
+    l[rs]               # L5R ring and skill dice
     |
     \[.*?\]             # Array
 ";
 
 const GENESYS_TOKEN_STRING: &str = r"(?x)
-    (?P<kind>[bsadpc])  # Kinds of dice, named capture group
+    (?P<kind>           # Kinds of dice, named capture group
+        dgd|dg|ud       # Downgrade difficulty, downgrade ability and upgrade difficulty
+        |
+        [bsadpcfu]      # Dice, or upgrade ability
+    )
     \s*                 # Allow any number of spaces
     (?P<number>\d+)     # Number of dice of the preceding kind, named capture group
 ";
@@ -44,6 +78,99 @@ m                   # No N-again (m for mortal, from Ex2)
 a(?P<again>\d+)     # N-again number
 ";
 
+const WOD_TOKEN_STRING: &str = r"(?x)
+    d(?:iff(?:iculty)?)?\s*(?P<difficulty>\d+)     # Difficulty
+    |
+    s(?:pec(?:ialty)?)?                             # Specialty: 10s count double
+";
+
+const IRONSWORN_TOKEN_STRING: &str = r"(?x)
+    progress\s*(?P<progress>\d+)    # Progress roll with this many filled boxes
+    |
+    (?P<modifier>[+-]?\s*\d+)       # Stat and adds for an action roll
+";
+
+const SR_TOKEN_STRING: &str = r"(?x)
+    e                   # Edge: Rule of Six and push the limit
+    |
+    l\s*(?P<limit>\d+)  # Limit
+";
+
+const L5R_TOKEN_STRING: &str = r"(?x)
+    (?P<rolled>\d+)\s*k\s*(?P<kept>\d+)   # Roll and keep (4e)
+    |
+    r\s*(?P<ring>\d+)    # Ring dice (5e)
+    |
+    s\s*(?P<skill>\d+)   # Skill dice (5e)
+    |
+    e                   # Emphasis (4e)
+    |
+    u                   # Unskilled (4e)
+";
+
+const DND_TOKEN_STRING: &str = r"(?x)
+    adv(?:antage)?      # Advantage
+    |
+    dis(?:advantage)?   # Disadvantage
+    |
+    elven               # Elven accuracy, advantage with three dice
+    |
+    crit                # Critical hit damage
+    |
+    dc\s*(?P<dc>\d+)    # Difficulty class
+";
+
+const DND_DICE_STRING: &str = r"\d+d\d+";
+
+const PBTA_TOKEN_STRING: &str = r"(?x)
+    adv(?:antage)?              # Advantage, roll three dice and keep the best two
+    |
+    dis(?:advantage)?           # Disadvantage, roll three dice and keep the worst two
+    |
+    weak\s*(?P<weak>\d+)        # Weak hit threshold
+    |
+    strong\s*(?P<strong>\d+)    # Strong hit threshold
+";
+
+const BLADES_TOKEN_STRING: &str = r"(?x)
+    fortune             # Fortune roll
+    |
+    resist(?:ance)?     # Resistance roll
+";
+
+const SWADE_TOKEN_STRING: &str = r"(?x)
+    d\s*(?P<sides>\d+)  # Trait die
+    |
+    tn\s*(?P<tn>\d+)    # Target number
+    |
+    extra               # Extras don't get a Wild Die
+";
+
+// Matches the rolls made by the year zero command, to find the base, skill and gear dice again when pushing
+const YEAR_ZERO_ROLL_STRING: &str = r"^\((?P<base>\d+)d6 & (?P<skill>\d+)d6 & (?P<gear>\d+)d6\)t6";
+
+const CORTEX_TOKEN_STRING: &str = r"(?x)
+    (?P<number>\d*)\s*d\s*(?P<sides>\d+)   # Dice of one size, like 2d8 or d6
+    |
+    effect                              # Pick the biggest effect die over the highest total
+";
+
+const COC_TOKEN_STRING: &str = r"(?x)
+    bonus\s*(?P<bonus>\d*)      # Bonus dice, one if no number is given
+    |
+    penalty\s*(?P<penalty>\d*)  # Penalty dice, one if no number is given
+    |
+    (?P<skill>\d+)              # Skill value
+";
+
+const WFRP_TOKEN_STRING: &str = r"(?x)
+    vs\s*(?P<opposed>\d+)           # Opponent's characteristic for an opposed test
+    |
+    (?P<modifier>[+-]\s*\d+)        # Difficulty modifier to the characteristic before it
+    |
+    (?P<characteristic>\d+)         # Characteristic or skill to roll under
+";
+
 const S3_TOKEN_STRING: &str = r"(?x)
     (?P<mod>[+-]\S+)    # Modifier or penalty
     |
@@ -55,5 +182,18 @@ lazy_static!{
     pub static ref GENESYS_TOKEN_RE: Regex = Regex::new(GENESYS_TOKEN_STRING).expect("Failed to compile genesys token regex!");
     pub static ref EXALTED_TOKEN_RE: Regex = Regex::new(EXALTED_TOKEN_STRING).expect("Failed to compile exalted token regex!");
     pub static ref COFD_TOKEN_RE: Regex = Regex::new(COFD_TOKEN_STRING).expect("Failed to compile cofd token regex!");
+    pub static ref WOD_TOKEN_RE: Regex = Regex::new(WOD_TOKEN_STRING).expect("Failed to compile wod token regex!");
+    pub static ref IRONSWORN_TOKEN_RE: Regex = Regex::new(IRONSWORN_TOKEN_STRING).expect("Failed to compile ironsworn token regex!");
+    pub static ref SR_TOKEN_RE: Regex = Regex::new(SR_TOKEN_STRING).expect("Failed to compile shadowrun token regex!");
+    pub static ref L5R_TOKEN_RE: Regex = Regex::new(L5R_TOKEN_STRING).expect("Failed to compile l5r token regex!");
+    pub static ref DND_TOKEN_RE: Regex = Regex::new(DND_TOKEN_STRING).expect("Failed to compile dnd token regex!");
+    pub static ref DND_DICE_RE: Regex = Regex::new(DND_DICE_STRING).expect("Failed to compile dnd dice regex!");
+    pub static ref PBTA_TOKEN_RE: Regex = Regex::new(PBTA_TOKEN_STRING).expect("Failed to compile pbta token regex!");
+    pub static ref BLADES_TOKEN_RE: Regex = Regex::new(BLADES_TOKEN_STRING).expect("Failed to compile blades token regex!");
+    pub static ref SWADE_TOKEN_RE: Regex = Regex::new(SWADE_TOKEN_STRING).expect("Failed to compile swade token regex!");
+    pub static ref YEAR_ZERO_ROLL_RE: Regex = Regex::new(YEAR_ZERO_ROLL_STRING).expect("Failed to compile year zero roll regex!");
+    pub static ref CORTEX_TOKEN_RE: Regex = Regex::new(CORTEX_TOKEN_STRING).expect("Failed to compile cortex token regex!");
+    pub static ref COC_TOKEN_RE: Regex = Regex::new(COC_TOKEN_STRING).expect("Failed to compile coc token regex!");
+    pub static ref WFRP_TOKEN_RE: Regex = Regex::new(WFRP_TOKEN_STRING).expect("Failed to compile wfrp token regex!");
     pub static ref S3_TOKEN_RE: Regex = Regex::new(S3_TOKEN_STRING).expect("Failed to compile story shaper token regex!");
 }
@@ -1,113 +1,156 @@
-use rand::{thread_rng, Rng};
+use rand::{Rng, RngCore};
 use std::fmt;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DieKind {
+    Standard,
+    Fudge,
+    Tens,
+    Units,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Die {
-    pub sides: u8,
-    pub result: u8,
+    pub sides: u16,
+    pub result: u16,
+    pub kind: DieKind,
 }
 
 impl Die {
-    pub fn roll(sides: u8) -> Die {
-        if sides == 0 { return Die { sides: 0, result: 0 }; }
-        let mut rng = thread_rng();
+    pub fn new(sides: u16, result: u16) -> Die {
+        Die { sides, result, kind: DieKind::Standard }
+    }
+
+    pub fn roll(sides: u16, rng: &mut dyn RngCore) -> Die {
+        if sides == 0 { return Die::new(0, 0); }
         let result = rng.gen_range(1..=sides);
-        Die { sides, result }
+        Die::new(sides, result)
     }
 
-    pub fn reroll(&mut self) {
-        let mut rng = thread_rng();
+    // Fudge dice are stored as d3s, with 1 for a minus, 2 for a blank and 3 for a plus
+    pub fn roll_fudge(rng: &mut dyn RngCore) -> Die {
+        Die { kind: DieKind::Fudge, ..Die::roll(3, rng) }
+    }
+
+    // Percentile dice are d10s read as 00-90 for the tens and 0-9 for the units, so a pool can pair them up again
+    pub fn roll_tens(rng: &mut dyn RngCore) -> Die {
+        Die { kind: DieKind::Tens, ..Die::roll(10, rng) }
+    }
+
+    pub fn roll_units(rng: &mut dyn RngCore) -> Die {
+        Die { kind: DieKind::Units, ..Die::roll(10, rng) }
+    }
+
+    pub fn reroll(&mut self, rng: &mut dyn RngCore) {
         self.result = rng.gen_range(1..=self.sides);
     }
 
-    pub fn reroll_better(&mut self) -> Die {
-        let new_die = self.explode();
+    pub fn reroll_better(&mut self, rng: &mut dyn RngCore) -> Die {
+        let new_die = self.explode(rng);
         if new_die > *self { self.set(new_die.result) };
         new_die
     }
 
-    pub fn reroll_worse(&mut self) -> Die {
-        let new_die = self.explode();
+    pub fn reroll_worse(&mut self, rng: &mut dyn RngCore) -> Die {
+        let new_die = self.explode(rng);
         if new_die < *self { self.set(new_die.result) };
         new_die
     }
 
-    pub fn reroll_excluding_single(&mut self, excluded: u8) {
-        let mut rng = thread_rng();
+    pub fn reroll_excluding_single(&mut self, excluded: u16, rng: &mut dyn RngCore) {
         let mut new_result = rng.gen_range(1..self.sides);
         if new_result >= excluded { new_result += 1; }
         self.set(new_result);
     }
 
-    pub fn reroll_excluding_range(&mut self, excluded: &[u8]) {
-        let mut rng = thread_rng();
-        let possible_values: Vec<u8> = (1..=self.sides).filter(|x| !excluded.contains(x)).collect();
+    pub fn reroll_excluding_range(&mut self, excluded: &[u16], rng: &mut dyn RngCore) {
+        let possible_values: Vec<u16> = (1..=self.sides).filter(|x| !excluded.contains(x)).collect();
         if possible_values.len() == 0 { return; }   // Leave the die alone if there are no possible values to reroll to
         let random_index = rng.gen_range(0..possible_values.len());
         self.set(possible_values[random_index]);
     }
 
-    pub fn set(&mut self, value: u8) {
+    pub fn set(&mut self, value: u16) {
         self.result = value
     }
 
-    pub fn explode(&self) -> Die {
-        Die::roll(self.sides)
+    pub fn explode(&self, rng: &mut dyn RngCore) -> Die {
+        Die { kind: self.kind, ..Die::roll(self.sides, rng) }
+    }
+
+    // What the die counts for when added up, which is just the result except for fudge dice
+    pub fn value(&self) -> i64 {
+        match self.kind {
+            DieKind::Standard => self.result as i64,
+            DieKind::Fudge => self.result as i64 - 2,
+            DieKind::Tens => (self.result % 10) as i64 * 10,
+            DieKind::Units => (self.result % 10) as i64,
+        }
     }
 
-    pub fn equals(&self, value: u8) -> bool {
+    pub fn equals(&self, value: u16) -> bool {
         self.result == value
     }
 
-    pub fn is_in(&self, range: &[u8]) -> bool {
+    pub fn is_in(&self, range: &[u16]) -> bool {
         range.contains(&self.result)
     }
 
-    pub fn equal_or_greater(&self, target: u8) -> bool {
+    pub fn equal_or_greater(&self, target: u16) -> bool {
         self.result >= target
     }
 
-    pub fn equal_or_less(&self, target: u8) -> bool {
+    pub fn equal_or_less(&self, target: u16) -> bool {
         self.result <= target
     }
 
-    pub fn count_successes(&self, tns: &[u8]) -> u8 {
+    pub fn count_successes(&self, tns: &[u16]) -> u16 {
         tns[(self.result-1) as usize]
     }
 }
 
 impl fmt::Display for Die {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.result)
+        match self.kind {
+            DieKind::Standard => write!(f, "{}", self.result),
+            DieKind::Fudge => match self.value() {
+                v if v > 0 => write!(f, "+"),
+                v if v < 0 => write!(f, "-"),
+                _ => write!(f, " "),
+            },
+            DieKind::Tens => write!(f, "{:02}", self.value()),
+            DieKind::Units => write!(f, "{}", self.value()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
     use super::*;
 
     #[test]
     fn set_test() {
-        let mut die = Die::roll(20);
+        let mut die = Die::roll(20, &mut StdRng::seed_from_u64(0));
         die.set(20);
-        assert_eq!(Die{sides: 20, result: 20}, die);
+        assert_eq!(Die { sides: 20, result: 20, kind: DieKind::Standard }, die);
     }
     
     #[test]
     fn test_eq() {
-        let die = Die {sides: 20, result: 10};
+        let die = Die { sides: 20, result: 10, kind: DieKind::Standard };
         assert!(die.equals(10));
     }
 
     #[test]
     fn test_geq() {
-        let die = Die {sides: 20, result: 15};
+        let die = Die { sides: 20, result: 15, kind: DieKind::Standard };
         assert!(die.equal_or_greater(13));
     }
 
     #[test]
     fn test_leq() {
-        let die = Die {sides: 20, result: 5};
+        let die = Die { sides: 20, result: 5, kind: DieKind::Standard };
         assert!(die.equal_or_less(8));
     }
 
@@ -116,11 +159,11 @@ mod tests {
         let sux_map = [0, 0, 0, 0, 0, 0, 1, 1, 1, 2];
         let sides = 10;
         let pool = [
-            Die { sides, result: 1 },
-            Die { sides, result: 3 },
-            Die { sides, result: 6 },
-            Die { sides, result: 7 },
-            Die { sides, result: 10 },
+            Die { sides, result: 1, kind: DieKind::Standard },
+            Die { sides, result: 3, kind: DieKind::Standard },
+            Die { sides, result: 6, kind: DieKind::Standard },
+            Die { sides, result: 7, kind: DieKind::Standard },
+            Die { sides, result: 10, kind: DieKind::Standard },
         ];
         
         let mut successes = 0;
@@ -130,4 +173,36 @@ mod tests {
 
         assert_eq!(3, successes)
     }
+
+    #[test]
+    fn test_seeded_rolls() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut replay_rng = StdRng::seed_from_u64(1234);
+
+        let rolls: Vec<Die> = (0..20).map(|_| Die::roll(20, &mut rng)).collect();
+        let replayed: Vec<Die> = (0..20).map(|_| Die::roll(20, &mut replay_rng)).collect();
+
+        assert_eq!(rolls, replayed);
+    }
+
+    #[test]
+    fn test_fudge() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let rolls: Vec<Die> = (0..30).map(|_| Die::roll_fudge(&mut rng)).collect();
+
+        assert!(rolls.iter().all(|d| (-1..=1).contains(&d.value())));
+        assert!(rolls.iter().all(|d| d.explode(&mut rng).kind == DieKind::Fudge));
+        assert_eq!(format!("{}", Die { sides: 3, result: 3, kind: DieKind::Fudge }), "+");
+        assert_eq!(format!("{}", Die { sides: 3, result: 2, kind: DieKind::Fudge }), " ");
+        assert_eq!(format!("{}", Die { sides: 3, result: 1, kind: DieKind::Fudge }), "-");
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(Die { sides: 10, result: 10, kind: DieKind::Tens }.value(), 0);
+        assert_eq!(Die { sides: 10, result: 4, kind: DieKind::Tens }.value(), 40);
+        assert_eq!(Die { sides: 10, result: 10, kind: DieKind::Units }.value(), 0);
+        assert_eq!(format!("{}", Die { sides: 10, result: 10, kind: DieKind::Tens }), "00");
+        assert_eq!(format!("{}", Die { sides: 10, result: 7, kind: DieKind::Units }), "7");
+    }
 }
@@ -0,0 +1,89 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    outcomes: Vec<(f64, f64)>,
+}
+
+impl Distribution {
+    pub fn new(outcomes: Vec<(f64, f64)>) -> Self {
+        let mut outcomes = outcomes;
+        outcomes.retain(|&(_, probability)| probability > 0.0);
+        outcomes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        // Merge outcomes with the same value so every value only appears once
+        let mut merged: Vec<(f64, f64)> = vec![];
+        for (value, probability) in outcomes {
+            match merged.last_mut() {
+                Some((last_value, last_probability)) if *last_value == value => *last_probability += probability,
+                _ => merged.push((value, probability)),
+            }
+        }
+
+        Distribution { outcomes: merged }
+    }
+
+    pub fn point(value: f64) -> Self {
+        Distribution { outcomes: vec![(value, 1.0)] }
+    }
+
+    pub fn outcomes(&self) -> &[(f64, f64)] {
+        &self.outcomes
+    }
+
+    pub fn point_value(&self) -> Option<f64> {
+        match self.outcomes.as_slice() {
+            [(value, _)] => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Distribution::new(self.outcomes.iter().map(|&(value, probability)| (f(value), probability)).collect())
+    }
+
+    pub fn combine(&self, other: &Distribution, f: impl Fn(f64, f64) -> f64) -> Self {
+        let mut outcomes = Vec::with_capacity(self.outcomes.len() * other.outcomes.len());
+        for &(left, p) in &self.outcomes {
+            for &(right, q) in &other.outcomes {
+                outcomes.push((f(left, right), p * q));
+            }
+        }
+
+        Distribution::new(outcomes)
+    }
+
+    // Should be 1, but might fall short of it if very unlikely outcomes had to be cut off
+    pub fn total_probability(&self) -> f64 {
+        self.outcomes.iter().map(|(_, probability)| probability).sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.outcomes.iter().map(|(value, probability)| value * probability).sum::<f64>() / self.total_probability()
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.outcomes.iter().map(|(value, probability)| (value - mean).powi(2) * probability).sum::<f64>() / self.total_probability();
+
+        variance.sqrt()
+    }
+
+    pub fn min(&self) -> f64 {
+        self.outcomes.first().map_or(f64::NAN, |(value, _)| *value)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.outcomes.last().map_or(f64::NAN, |(value, _)| *value)
+    }
+
+    pub fn p_at_least(&self, threshold: f64) -> f64 {
+        self.outcomes.iter().filter(|(value, _)| *value >= threshold).map(|(_, probability)| probability).sum::<f64>() / self.total_probability()
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mean {:.2}, standard deviation {:.2}", self.mean(), self.std_dev())
+    }
+}
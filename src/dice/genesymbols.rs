@@ -1,4 +1,8 @@
-use super::die::Die;
+use std::{fmt, str::FromStr};
+use super::{
+    dice_errors::RollError,
+    die::Die,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GeneSymbol {
@@ -8,10 +12,44 @@ pub enum GeneSymbol {
     Failure,
     Threat,
     Despair,
+    LightSide,
+    DarkSide,
     Blank,
 }
 
 impl GeneSymbol {
+    // The order symbols are reported in, good news first and force pips last
+    pub const ORDER: [GeneSymbol; 8] = [
+        GeneSymbol::Success,
+        GeneSymbol::Advantage,
+        GeneSymbol::Triumph,
+        GeneSymbol::Failure,
+        GeneSymbol::Threat,
+        GeneSymbol::Despair,
+        GeneSymbol::LightSide,
+        GeneSymbol::DarkSide,
+    ];
+
+    pub fn name(&self, number: u8) -> &'static str {
+        match (self, number) {
+            (GeneSymbol::Success, 1) => "Success",
+            (GeneSymbol::Success, _) => "Successes",
+            (GeneSymbol::Triumph, 1) => "Triumph",
+            (GeneSymbol::Triumph, _) => "Triumphs",
+            (GeneSymbol::Failure, 1) => "Failure",
+            (GeneSymbol::Failure, _) => "Failures",
+            (GeneSymbol::Despair, 1) => "Despair",
+            (GeneSymbol::Despair, _) => "Despairs",
+            (GeneSymbol::Advantage, 1) => "Advantage",
+            (GeneSymbol::Advantage, _) => "Advantages",
+            (GeneSymbol::Threat, 1) => "Threat",
+            (GeneSymbol::Threat, _) => "Threats",
+            (GeneSymbol::LightSide, _) => "Light Side",
+            (GeneSymbol::DarkSide, _) => "Dark Side",
+            (GeneSymbol::Blank, _) => "Blank",
+        }
+    }
+
     pub fn boost(die: Die) -> Vec<Self> {
         match die.result {
             3 => vec![GeneSymbol::Success],
@@ -77,4 +115,41 @@ impl GeneSymbol {
             _ => vec![GeneSymbol::Blank],
         }
     }
+
+    // Star Wars force die. Light and dark side pips never cancel anything out
+    pub fn force(die: Die) -> Vec<Self> {
+        match die.result {
+            1..=6 => vec![GeneSymbol::DarkSide],
+            7 => vec![GeneSymbol::DarkSide, GeneSymbol::DarkSide],
+            8 | 9 => vec![GeneSymbol::LightSide],
+            10..=12 => vec![GeneSymbol::LightSide, GeneSymbol::LightSide],
+            _ => vec![GeneSymbol::Blank],
+        }
+    }
+}
+
+impl FromStr for GeneSymbol {
+    type Err = RollError;
+
+    // Symbol names as they're written in the config file
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace([' ', '_'], "").as_str() {
+            "success" => Ok(GeneSymbol::Success),
+            "advantage" => Ok(GeneSymbol::Advantage),
+            "triumph" => Ok(GeneSymbol::Triumph),
+            "failure" => Ok(GeneSymbol::Failure),
+            "threat" => Ok(GeneSymbol::Threat),
+            "despair" => Ok(GeneSymbol::Despair),
+            "lightside" => Ok(GeneSymbol::LightSide),
+            "darkside" => Ok(GeneSymbol::DarkSide),
+            "blank" => Ok(GeneSymbol::Blank),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for GeneSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name(1))
+    }
 }
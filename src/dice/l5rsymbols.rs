@@ -0,0 +1,35 @@
+use super::die::Die;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum L5rSymbol {
+    Success,
+    ExplosiveSuccess,
+    Opportunity,
+    Strife,
+    Blank,
+}
+
+impl L5rSymbol {
+    pub fn ring(die: Die) -> Vec<Self> {
+        match die.result {
+            2 => vec![L5rSymbol::Opportunity, L5rSymbol::Strife],
+            3 => vec![L5rSymbol::Opportunity],
+            4 => vec![L5rSymbol::Success, L5rSymbol::Strife],
+            5 => vec![L5rSymbol::Success],
+            6 => vec![L5rSymbol::ExplosiveSuccess, L5rSymbol::Strife],
+            _ => vec![L5rSymbol::Blank],
+        }
+    }
+
+    pub fn skill(die: Die) -> Vec<Self> {
+        match die.result {
+            3..=5 => vec![L5rSymbol::Opportunity],
+            6 | 7 => vec![L5rSymbol::Success, L5rSymbol::Strife],
+            8 | 9 => vec![L5rSymbol::Success],
+            10 => vec![L5rSymbol::Success, L5rSymbol::Opportunity],
+            11 => vec![L5rSymbol::ExplosiveSuccess, L5rSymbol::Strife],
+            12 => vec![L5rSymbol::ExplosiveSuccess],
+            _ => vec![L5rSymbol::Blank],
+        }
+    }
+}
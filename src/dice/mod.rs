@@ -1,4 +1,5 @@
 pub mod die;
+pub mod distribution;
 pub mod dice_errors;
 pub mod dice_re;
 pub mod pool;
@@ -7,7 +8,10 @@ pub mod roll_token;
 pub mod token_kinds;
 pub mod tray;
 pub mod genesymbols;
+pub mod l5rsymbols;
+pub mod odds;
 pub mod roll_value;
 pub mod value_kinds;
 pub mod roll_stack;
+pub mod simulation;
 pub mod command_translations;
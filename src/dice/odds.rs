@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+use crate::math::{rpn_token::RpnToken, math_errors::MathError};
+use super::{
+    dice_errors::RollError,
+    die::DieKind,
+    distribution::Distribution,
+    roll_token::*,
+};
+
+// Give up on an exact answer rather than track more distinct outcomes than this for a single group of dice
+const MAX_OUTCOMES: usize = 200_000;
+// Likewise for the number of pairs of outcomes looked at when adding two independent results together
+const MAX_COMBINATIONS: usize = 20_000_000;
+// Recursive explosions are followed until the chance of getting that far drops below this
+const EXPLOSION_CUTOFF: f64 = 1e-10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Face {
+    result: u32,
+    sides: u16,
+    kind: DieKind,
+}
+
+impl Face {
+    fn value(&self) -> f64 {
+        match self.kind {
+            DieKind::Standard => self.result as f64,
+            DieKind::Fudge => self.result as f64 - 2.0,
+            DieKind::Tens => (self.result % 10) as f64 * 10.0,
+            DieKind::Units => (self.result % 10) as f64,
+        }
+    }
+}
+
+// Every possible (sorted) set of faces a group of dice can show, with its probability.
+// Groups are independent of each other; dice only end up in the same group when an operation ties them together.
+type Unit = Vec<(Vec<Face>, f64)>;
+
+#[derive(Clone, Debug)]
+enum Rule {
+    AtLeast(u32),
+    AtMost(u32),
+    Map(Vec<u16>, i32),
+}
+
+impl Rule {
+    fn score(&self, face: &Face) -> i32 {
+        match self {
+            Rule::AtLeast(target) => if face.result >= *target { 1 } else { 0 },
+            Rule::AtMost(target) => if face.result <= *target { -1 } else { 0 },
+            Rule::Map(map, sign) => sign * *map.get((face.result as usize).wrapping_sub(1)).unwrap_or(&0) as i32,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Valuation {
+    Sum,
+    Successes(Vec<Rule>),
+    Fixed(Distribution),
+}
+
+#[derive(Clone, Debug)]
+enum Term {
+    Number(Distribution),
+    Array(Vec<u16>),
+    Fudge,
+    Dice { units: Vec<Unit>, valuation: Valuation },
+}
+
+impl Term {
+    fn distribution(&self) -> Result<Distribution, RollError> {
+        match self {
+            Term::Number(distribution) => Ok(distribution.clone()),
+            Term::Array(_) => Err(RollError::NotImplementedError),
+            Term::Fudge => Err(RollError::NotANumberError),
+            Term::Dice { units, valuation } => match valuation {
+                Valuation::Sum => units_distribution(units, |face| face.value()),
+                Valuation::Successes(rules) => units_distribution(units, |face| rules.iter().map(|r| r.score(face)).sum::<i32>() as f64),
+                Valuation::Fixed(distribution) => Ok(distribution.clone()),
+            },
+        }
+    }
+
+    fn argument(self) -> Result<Argument, RollError> {
+        match self {
+            Term::Array(array) => Ok(Argument::Array(array)),
+            Term::Fudge => Ok(Argument::Fudge),
+            Term::Number(distribution) => {
+                let value = distribution.point_value().ok_or(RollError::IntractableError)?;
+                if value.fract() == 0.0 && value >= 0.0 {
+                    Argument::from_decimal(value)
+                } else {
+                    Err(RollError::ArgumentError)
+                }
+            },
+            dice => Argument::from_decimal(dice.distribution()?.point_value().ok_or(RollError::IntractableError)?),
+        }
+    }
+
+    fn units(self) -> Result<Vec<Unit>, RollError> {
+        match self {
+            Term::Dice { units, valuation: _ } => Ok(units),
+            _ => Err(RollError::MissingPoolError),
+        }
+    }
+}
+
+pub fn evaluate_string(infix_expression: &str) -> Result<Distribution, RollError> {
+    let infix_tokens = RollToken::tokenize_expression(infix_expression)?;
+
+    evaluate_tokens(&infix_tokens)
+}
+
+pub fn evaluate_tokens(infix_tokens: &[RollToken]) -> Result<Distribution, RollError> {
+    let postfix_tokens = RollToken::shunting_dice(infix_tokens)?;
+
+    resolve_rpn(&postfix_tokens)
+}
+
+// Walks the same postfix tokens as RollStack::resolve_rpn, but keeps track of every possible result instead of rolling
+pub fn resolve_rpn(postfix_tokens: &[RollToken]) -> Result<Distribution, RollError> {
+    let mut stack: Vec<Term> = vec![];
+
+    for token in postfix_tokens {
+        match token {
+            RollToken::Math(rpn_token) => {
+                match rpn_token {
+                    RpnToken::Number(number) => stack.push(Term::Number(Distribution::point(*number))),
+                    RpnToken::Operator(operator) => {
+                        let right = stack.pop().ok_or(MathError::OperatorMismatchError)?.distribution()?;
+                        let left = stack.pop().ok_or(MathError::OperatorMismatchError)?.distribution()?;
+                        stack.push(Term::Number(combine(&left, &right, |l, r| operator.apply(l, r))?));
+                    },
+                    RpnToken::MathFn(math_fn) => {
+                        let arg = stack.pop().ok_or(MathError::FnMismatchError)?.distribution()?;
+                        stack.push(Term::Number(arg.map(|a| math_fn.apply(a))));
+                    },
+                    _ => return Err(RollError::MathError(MathError::MisplacedTokenError(rpn_token.clone()))),
+                }
+            },
+            RollToken::Argument(argument) => match argument {
+                Argument::Single(number) => stack.push(Term::Number(Distribution::point(*number as f64))),
+                Argument::Array(array) => stack.push(Term::Array(array.clone())),
+                Argument::Fudge => stack.push(Term::Fudge),
+                // Percentile dice are read in pairs rather than added up, which dice distributions don't track
+                Argument::Percentile => return Err(RollError::IntractableError),
+            },
+            RollToken::Dice(Dice { pool: Some(pool) }) => {
+                let units = pool.dice().iter().map(|die| vec![(vec![Face { result: die.result as u32, sides: die.sides, kind: die.kind }], 1.0)]).collect();
+                stack.push(Term::Dice { units, valuation: Valuation::Sum });
+            },
+            RollToken::Dice(_) => {
+                let right = stack.pop().ok_or(MathError::OperatorMismatchError)?.argument()?;
+                let left = stack.pop().ok_or(MathError::OperatorMismatchError)?.argument()?;
+                stack.push(Term::Dice { units: roll_dice(left, right)?, valuation: Valuation::Sum });
+            },
+            RollToken::Operator(operator) => {
+                let right = stack.pop().ok_or(MathError::OperatorMismatchError)?.argument()?;
+                let left = stack.pop().ok_or(MathError::OperatorMismatchError)?;
+                stack.push(apply_operator(operator, left, right)?);
+            },
+            RollToken::Conversion(_) => return Err(RollError::IntractableError),
+            RollToken::Combination(Combination::Merge(_)) => {
+                let right = stack.pop().ok_or(MathError::OperatorMismatchError)?;
+                let left = stack.pop().ok_or(MathError::OperatorMismatchError)?;
+                let value = combine(&left.distribution()?, &right.distribution()?, |l, r| l + r)?;
+                match (left, right) {
+                    (Term::Dice { units: mut left_units, valuation: _ }, Term::Dice { units: right_units, valuation: _ }) => {
+                        left_units.extend(right_units);
+                        stack.push(Term::Dice { units: left_units, valuation: Valuation::Fixed(value) });
+                    },
+                    _ => stack.push(Term::Number(value)),
+                }
+            },
+        }
+    }
+
+    if stack.len() != 1 {
+        Err(RollError::MathError(MathError::TrailingTokensError))
+    } else {
+        stack.pop().ok_or(MathError::ImpossibleError)?.distribution()
+    }
+}
+
+fn roll_dice(left: Argument, right: Argument) -> Result<Vec<Unit>, RollError> {
+    let (numbers, sides) = match (left, right) {
+        (Argument::Fudge | Argument::Percentile, _) => return Err(RollError::ArgumentError),
+        (_, Argument::Percentile) => return Err(RollError::IntractableError),
+        (Argument::Single(number), Argument::Fudge) => return Ok(vec![fudge_die(); number as usize]),
+        (Argument::Array(numbers), Argument::Fudge) => return Ok(vec![fudge_die(); numbers.iter().map(|&n| n as usize).sum()]),
+        (Argument::Single(number), Argument::Single(sides)) => (vec![number as u32], vec![sides]),
+        (Argument::Single(number), Argument::Array(sides)) => (vec![number as u32; sides.len()], sides),
+        (Argument::Array(numbers), Argument::Single(sides)) => (vec![numbers.iter().map(|&n| n as u32).sum()], vec![sides]),
+        (Argument::Array(numbers), Argument::Array(sides)) => (numbers.iter().map(|&n| n as u32).collect(), sides),
+    };
+
+    let mut units = vec![];
+    for (&number, &sides) in numbers.iter().zip(sides.iter()) {
+        for _ in 0..number {
+            units.push(single_die(sides));
+        }
+    }
+
+    Ok(units)
+}
+
+fn single_die(sides: u16) -> Unit {
+    if sides == 0 { return vec![(vec![Face { result: 0, sides: 0, kind: DieKind::Standard }], 1.0)]; }
+    faces_of(sides, &[]).into_iter().map(|face| (vec![face], 1.0 / sides as f64)).collect()
+}
+
+fn fudge_die() -> Unit {
+    single_die(3).into_iter().map(|(faces, p)| (faces.into_iter().map(|face| Face { kind: DieKind::Fudge, ..face }).collect(), p)).collect()
+}
+
+fn faces_of(sides: u16, excluded: &[u32]) -> Vec<Face> {
+    (1..=sides as u32).filter(|r| !excluded.contains(r)).map(|result| Face { result, sides, kind: DieKind::Standard }).collect()
+}
+
+fn apply_operator(operator: &Operator, token: Term, argument: Argument) -> Result<Term, RollError> {
+    let range: Vec<u32> = match &argument {
+        Argument::Single(n) => vec![*n as u32],
+        Argument::Array(array) => array.iter().map(|&n| n as u32).collect(),
+        Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+    };
+
+    match operator {
+        Operator::Cap(cap) => {
+            // Caps per die size depend on the order the sizes were rolled in, which isn't tracked here
+            let limit = match argument {
+                Argument::Array(array) if array.len() != 1 => return Err(RollError::IntractableError),
+                _ => range[0],
+            };
+            let units = transform_dice(&token.units()?, |face| match cap {
+                Cap::Max { .. } => vec![(vec![Face { result: face.result.min(limit), ..face }], 1.0)],
+                Cap::Min { .. } => vec![(vec![Face { result: face.result.max(limit), ..face }], 1.0)],
+            })?;
+            Ok(Term::Dice { units, valuation: Valuation::Sum })
+        },
+        Operator::Explode(explode) => {
+            let units = token.units()?;
+            let sides_max = max_sides(&units);
+            let blocked = match argument {
+                Argument::Array(_) => range.len() > (sides_max / 2).into(),
+                _ => sides_max == 1,
+            };
+            let units = match explode {
+                Explode::Once { .. } => transform_dice(&units, |face| {
+                    if !range.contains(&face.result) { return vec![(vec![face], 1.0)]; }
+                    explosions(face.sides, &range, 1.0, false).into_iter().map(|(mut extra, p)| { extra.push(face); (extra, p) }).collect()
+                })?,
+                Explode::Recursive { .. } if blocked => return Err(RollError::BlockedExplosionError),
+                Explode::Recursive { .. } => transform_dice(&units, |face| {
+                    if !range.contains(&face.result) { return vec![(vec![face], 1.0)]; }
+                    explosions(face.sides, &range, 1.0, true).into_iter().map(|(mut extra, p)| { extra.push(face); (extra, p) }).collect()
+                })?,
+                Explode::Additive { .. } if blocked => return Err(RollError::BlockedExplosionError),
+                Explode::Additive { .. } => transform_dice(&units, |face| {
+                    if !range.contains(&face.result) { return vec![(vec![face], 1.0)]; }
+                    additive_explosions(face.sides, &range, 1.0).into_iter().map(|(added, p)| (vec![Face { result: face.result + added, ..face }], p)).collect()
+                })?,
+            };
+            Ok(Term::Dice { units, valuation: Valuation::Sum })
+        },
+        Operator::Keep(keep) => {
+            let units = token.units()?;
+            let units = match keep {
+                Keep::Exact { .. } => transform_dice(&units, |face| match range.contains(&face.result) {
+                    true => vec![(vec![face], 1.0)],
+                    false => vec![(vec![], 1.0)],
+                })?,
+                Keep::High { .. } | Keep::Low { .. } => {
+                    let amount = match argument {
+                        Argument::Array(array) if array.len() != 1 => return Err(RollError::ArgumentError),
+                        _ => range[0] as usize,
+                    };
+                    vec![keep_dice(&units, amount, matches!(keep, Keep::High { .. }))?]
+                },
+            };
+            Ok(Term::Dice { units, valuation: Valuation::Sum })
+        },
+        // Dropping dice is keeping the rest, as long as every unit always comes out to the same number of dice
+        Operator::Drop(drop) => {
+            let units = token.units()?;
+            let amount = match argument {
+                Argument::Array(array) if array.len() != 1 => return Err(RollError::ArgumentError),
+                _ => range[0] as usize,
+            };
+            let mut total = 0;
+            for unit in &units {
+                let counts: Vec<usize> = unit.iter().map(|(faces, _)| faces.len()).collect();
+                if counts.iter().any(|&count| count != counts[0]) { return Err(RollError::IntractableError); }
+                total += counts.first().copied().unwrap_or(0);
+            }
+            let units = vec![keep_dice(&units, total.saturating_sub(amount), matches!(drop, Drop::Low { .. }))?];
+            Ok(Term::Dice { units, valuation: Valuation::Sum })
+        },
+        Operator::Reroll(reroll) => {
+            let units = token.units()?;
+            let units = transform_dice(&units, |face| {
+                if !range.contains(&face.result) { return vec![(vec![face], 1.0)]; }
+                let rerolls = match reroll {
+                    Reroll::Once { .. } => faces_of(face.sides, &[]),
+                    Reroll::Better { .. } => faces_of(face.sides, &[]).into_iter().map(|new| Face { result: new.result.max(face.result), ..face }).collect(),
+                    Reroll::Worse { .. } => faces_of(face.sides, &[]).into_iter().map(|new| Face { result: new.result.min(face.result), ..face }).collect(),
+                    Reroll::Recursive { .. } => faces_of(face.sides, &range),
+                };
+                if rerolls.is_empty() { return vec![(vec![face], 1.0)]; }
+                let p = 1.0 / rerolls.len() as f64;
+                rerolls.into_iter().map(|new| (vec![new], p)).collect()
+            })?;
+            Ok(Term::Dice { units, valuation: Valuation::Sum })
+        },
+        Operator::Target(target) => {
+            let mut rules = match &token {
+                Term::Dice { units: _, valuation: Valuation::Successes(rules) } => rules.clone(),
+                _ => vec![],
+            };
+            let units = token.units()?;
+            match (target, argument) {
+                (Target::Success { .. }, Argument::Single(threshold)) => rules.push(Rule::AtLeast(threshold as u32)),
+                (Target::Botch { .. }, Argument::Single(threshold)) => rules.push(Rule::AtMost(threshold as u32)),
+                (Target::Success { .. }, Argument::Array(threshold_array)) => {
+                    let max_sides = max_sides(&units) as usize;
+                    let mut tns = vec![0; max_sides];
+                    if tns.len() >= threshold_array.len() {
+                        tns[max_sides - threshold_array.len()..].copy_from_slice(&threshold_array);
+                    } else {
+                        tns.copy_from_slice(&threshold_array[..max_sides]);
+                    }
+                    rules = vec![Rule::Map(tns, 1)];
+                },
+                (Target::Botch { .. }, Argument::Array(threshold_array)) => rules = vec![Rule::Map(threshold_array, -1)],
+                // Success Levels depend on the tens digit of a percentile roll rather than counting dice
+                (Target::Wfrp { .. }, _) => return Err(RollError::IntractableError),
+                (_, Argument::Fudge | Argument::Percentile) => return Err(RollError::ArgumentError),
+            }
+            Ok(Term::Dice { units, valuation: Valuation::Successes(rules) })
+        },
+        Operator::Coc(_) => Err(RollError::IntractableError),
+        // Comparing against the challenge dice depends on each die in the pool
+        Operator::Ironsworn(_) => Err(RollError::IntractableError),
+        // Sets depend on which faces come up together
+        Operator::Ore(_) => Err(RollError::IntractableError),
+        // Picking the effect die depends on the whole pool at once
+        Operator::Cortex(_) => Err(RollError::IntractableError),
+        // Sorting results into tiers doesn't change the total
+        Operator::Pbta(_) => Ok(Term::Number(token.distribution()?)),
+        Operator::Shadowrun(_) => {
+            let limit = match argument {
+                Argument::Single(limit) => limit as f64,
+                _ => return Err(RollError::ArgumentError),
+            };
+            let hits = Term::Dice { units: token.units()?, valuation: Valuation::Successes(vec![Rule::AtLeast(5)]) }.distribution()?;
+            match limit {
+                l if l > 0.0 => Ok(Term::Number(hits.map(|h| h.min(l)))),
+                _ => Ok(Term::Number(hits)),
+            }
+        },
+    }
+}
+
+fn max_sides(units: &[Unit]) -> u16 {
+    units.iter().flatten().flat_map(|(faces, _)| faces.iter()).map(|face| face.sides).max().unwrap_or(0)
+}
+
+// Replace every die in every group with each of the sets of dice it can turn into
+fn transform_dice(units: &[Unit], f: impl Fn(Face) -> Vec<(Vec<Face>, f64)>) -> Result<Vec<Unit>, RollError> {
+    let mut new_units = vec![];
+
+    for unit in units {
+        let mut outcomes: HashMap<Vec<Face>, f64> = HashMap::new();
+        for (faces, probability) in unit {
+            let mut partial = vec![(vec![], *probability)];
+            for &face in faces {
+                let results = f(face);
+                let mut next = Vec::with_capacity(partial.len() * results.len());
+                for (done, p) in &partial {
+                    for (new_faces, q) in &results {
+                        let mut combined: Vec<Face> = done.clone();
+                        combined.extend_from_slice(new_faces);
+                        next.push((combined, p * q));
+                    }
+                }
+                if next.len() > MAX_OUTCOMES { return Err(RollError::IntractableError); }
+                partial = next;
+            }
+            for (mut new_faces, p) in partial {
+                new_faces.sort();
+                *outcomes.entry(new_faces).or_insert(0.0) += p;
+            }
+            if outcomes.len() > MAX_OUTCOMES { return Err(RollError::IntractableError); }
+        }
+        new_units.push(outcomes.into_iter().collect());
+    }
+
+    Ok(new_units)
+}
+
+// All the sets of extra dice that one exploding die of the given size can add to the pool
+fn explosions(sides: u16, range: &[u32], probability: f64, recursive: bool) -> Vec<(Vec<Face>, f64)> {
+    let mut results = vec![];
+    let p = probability / sides as f64;
+
+    for new in faces_of(sides, &[]) {
+        if recursive && range.contains(&new.result) && p >= EXPLOSION_CUTOFF {
+            for (mut extra, q) in explosions(sides, range, p, recursive) {
+                extra.push(new);
+                results.push((extra, q * p / probability));
+            }
+        } else {
+            results.push((vec![new], p / probability));
+        }
+    }
+
+    results
+}
+
+// All the amounts an additive explosion can add to a die, which keeps adding as long as it rolls in the range
+fn additive_explosions(sides: u16, range: &[u32], probability: f64) -> Vec<(u32, f64)> {
+    let mut results = vec![];
+    let p = probability / sides as f64;
+
+    for new in faces_of(sides, &[]) {
+        if range.contains(&new.result) && p >= EXPLOSION_CUTOFF {
+            for (added, q) in additive_explosions(sides, range, p) {
+                results.push((new.result + added, q * p / probability));
+            }
+        } else {
+            results.push((new.result, p / probability));
+        }
+    }
+
+    results
+}
+
+// Keeping dice compares dice across groups, so all the groups get folded into one that only tracks the kept dice
+fn keep_dice(units: &[Unit], amount: usize, high: bool) -> Result<Unit, RollError> {
+    let mut states: HashMap<Vec<Face>, f64> = HashMap::new();
+    states.insert(vec![], 1.0);
+
+    for unit in units {
+        let mut next: HashMap<Vec<Face>, f64> = HashMap::new();
+        for (kept, p) in &states {
+            for (faces, q) in unit {
+                let mut merged = kept.clone();
+                merged.extend_from_slice(faces);
+                merged.sort();
+                if merged.len() > amount {
+                    merged = match high {
+                        true => merged.split_off(merged.len() - amount),
+                        false => { merged.truncate(amount); merged },
+                    };
+                }
+                *next.entry(merged).or_insert(0.0) += p * q;
+            }
+        }
+        if next.len() > MAX_OUTCOMES { return Err(RollError::IntractableError); }
+        states = next;
+    }
+
+    Ok(states.into_iter().collect())
+}
+
+fn units_distribution(units: &[Unit], score: impl Fn(&Face) -> f64) -> Result<Distribution, RollError> {
+    units.iter().try_fold(Distribution::point(0.0), |total, unit| {
+        let unit_distribution = Distribution::new(unit.iter().map(|(faces, p)| (faces.iter().map(&score).sum(), *p)).collect());
+        combine(&total, &unit_distribution, |l, r| l + r)
+    })
+}
+
+fn combine(left: &Distribution, right: &Distribution, f: impl Fn(f64, f64) -> f64) -> Result<Distribution, RollError> {
+    if left.outcomes().len() * right.outcomes().len() > MAX_COMBINATIONS { return Err(RollError::IntractableError); }
+
+    Ok(left.combine(right, f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left: f64, right: f64) {
+        assert!((left - right).abs() < 1e-6, "{} is not close to {}", left, right);
+    }
+
+    #[test]
+    fn test_sums() {
+        let two_d6 = evaluate_string("2d6").unwrap();
+        assert_close(two_d6.mean(), 7.0);
+        assert_close(two_d6.p_at_least(7.0), 21.0 / 36.0);
+        assert_close(evaluate_string("1d6+1d8*2").unwrap().mean(), 12.5);
+        assert_close(evaluate_string("2d6 & 3d8").unwrap().mean(), 20.5);
+        assert_eq!(evaluate_string("{3, 5, 6}d6k2 + 1d4").unwrap().min(), 12.0);
+        assert_close(evaluate_string("4df").unwrap().p_at_least(4.0), 1.0 / 81.0);
+        assert_eq!(evaluate_string("4df + 2").unwrap().min(), -2.0);
+    }
+
+    #[test]
+    fn test_keep() {
+        assert_close(evaluate_string("2d20kh1").unwrap().mean(), 13.825);
+        assert_close(evaluate_string("2d20kl1").unwrap().mean(), 7.175);
+        assert_close(evaluate_string("4d6k3").unwrap().mean(), 15869.0 / 1296.0);
+        assert_close(evaluate_string("4d6ke[5, 6]").unwrap().mean(), 4.0 * 11.0 / 6.0);
+        assert_close(evaluate_string("4d6dl1").unwrap().mean(), 15869.0 / 1296.0);
+        assert_close(evaluate_string("2d20dh1").unwrap().mean(), evaluate_string("2d20kl1").unwrap().mean());
+        assert!(matches!(evaluate_string("4d6e6dl1"), Err(RollError::IntractableError)));
+    }
+
+    #[test]
+    fn test_rerolls_and_explosions() {
+        assert_close(evaluate_string("1d6r1").unwrap().mean(), 3.5 + 2.5 / 6.0);
+        assert_close(evaluate_string("1d6rr1").unwrap().mean(), 4.0);
+        assert_close(evaluate_string("1d6rb[1, 2, 3]").unwrap().mean(), 157.0 / 36.0);
+        assert_close(evaluate_string("1d6e6").unwrap().mean(), 3.5 + 3.5 / 6.0);
+        assert_close(evaluate_string("1d6er6").unwrap().mean(), 4.2);
+        assert_close(evaluate_string("1d6ea6").unwrap().mean(), 4.2);
+    }
+
+    #[test]
+    fn test_caps() {
+        assert_close(evaluate_string("1d6cl2").unwrap().mean(), 22.0 / 6.0);
+        assert_close(evaluate_string("2d6c5").unwrap().mean(), 2.0 * 20.0 / 6.0);
+        assert!(matches!(evaluate_string("[1, 1]d[6, 8]cmax[4, 5]"), Err(RollError::IntractableError)));
+    }
+
+    #[test]
+    fn test_successes() {
+        assert_close(evaluate_string("5d10t8").unwrap().mean(), 1.5);
+        assert_close(evaluate_string("5d10t6b1").unwrap().mean(), 2.0);
+        assert_close(evaluate_string("4d10t[1, 1, 1, 2]").unwrap().mean(), 2.0);
+        assert_close(evaluate_string("1d10er10t8").unwrap().mean(), 0.3 / 0.9);
+        assert_close(evaluate_string("1d10er10").unwrap().mean(), 5.5 / 0.9);
+        assert_close(evaluate_string("3d6sr0").unwrap().mean(), 1.0);
+        assert_close(evaluate_string("2d6sr1").unwrap().p_at_least(1.0), 5.0 / 9.0);
+        assert_eq!(evaluate_string("6d6sr2").unwrap().max(), 2.0);
+    }
+
+    #[test]
+    fn test_intractable() {
+        assert!(matches!(evaluate_string("2d8ga"), Err(RollError::IntractableError)));
+        assert!(matches!(evaluate_string("1d4d6"), Err(RollError::IntractableError)));
+    }
+}
@@ -1,25 +1,31 @@
 use super::{
-    die::Die,
+    die::{Die, DieKind},
     dice_errors::RollError,
+    roll_value::RollValue,
 };
+use rand::RngCore;
 use std::{
+    cmp::Reverse,
     fmt,
     str::FromStr,
 };
 
+// Largest number of dice a single pool can hold, so that counts fit in a u16 and totals in a u32
+pub const MAX_DICE: usize = u16::MAX as usize;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Pool {
-    numbers: Vec<u8>,
-    sides: Vec<u8>,
+    numbers: Vec<u16>,
+    sides: Vec<u16>,
     dice: Vec<Die>,
 }
 
 impl Pool {
-    pub fn new(number: u8, sides: u8) -> Self {
+    pub fn new(number: u16, sides: u16, rng: &mut dyn RngCore) -> Self {
         let mut dice = Vec::<Die>::new();
 
         for _ in 0..number {
-            let die = Die::roll(sides);
+            let die = Die::roll(sides, rng);
             dice.push(die);
         }
         // Don't need to order by sides here because there is only one type of dice
@@ -30,12 +36,30 @@ impl Pool {
         Pool { numbers, sides, dice }
     }
 
-    pub fn new_from_arrays(number: &[u8], sides: &[u8]) -> Self {
+    pub fn new_fudge(number: u16, rng: &mut dyn RngCore) -> Self {
+        let dice = (0..number).map(|_| Die::roll_fudge(rng)).collect();
+
+        Pool { numbers: vec![number], sides: vec![3], dice }
+    }
+
+    // Tens dice first and the units die last, so each tens die can be read together with the units die
+    pub fn new_percentile(tens: u16, rng: &mut dyn RngCore) -> Self {
+        let mut dice: Vec<Die> = (0..tens).map(|_| Die::roll_tens(rng)).collect();
+        dice.push(Die::roll_units(rng));
+
+        Pool { numbers: vec![tens + 1], sides: vec![10], dice }
+    }
+
+    pub fn empty() -> Self {
+        Pool { numbers: vec![0], sides: vec![0], dice: vec![] }
+    }
+
+    pub fn new_from_arrays(number: &[u16], sides: &[u16], rng: &mut dyn RngCore) -> Self {
         let mut dice = vec![];
 
         for (&n, &s) in number.iter().zip(sides.iter()) {
             for _ in 0..n {
-                dice.push(Die::roll(s));
+                dice.push(Die::roll(s, rng));
             }
         }
         // Order by sides. This lets the rest of the code assume that pools will always be ordered from smallest to biggest die
@@ -45,16 +69,16 @@ impl Pool {
         Pool { numbers: number.to_vec(), sides: sides.to_vec(), dice }
     }
 
-    pub fn new_dice_array(number: u8, sides: &[u8]) -> Self {
+    pub fn new_dice_array(number: u16, sides: &[u16], rng: &mut dyn RngCore) -> Self {
         let number_arr = vec![number; sides.len()];
 
-        Pool::new_from_arrays(&number_arr, sides)
+        Pool::new_from_arrays(&number_arr, sides, rng)
     }
 
-    pub fn new_numbers_array(number: &[u8], sides: u8) -> Self {
-        let number_single = number.iter().fold(0, |n, s| n + s);
+    pub fn new_numbers_array(number: &[u16], sides: u16, rng: &mut dyn RngCore) -> Self {
+        let number_single = number.iter().fold(0, |n: u16, s| n.saturating_add(*s));
 
-        Pool::new(number_single, sides)
+        Pool::new(number_single, sides, rng)
     }
 
     pub fn new_from_dice(dice: &[Die]) -> Self {
@@ -85,29 +109,58 @@ impl Pool {
         &self.dice
     }
 
-    pub fn total_number(&self) -> u8 {
-        self.dice().len() as u8
+    pub fn total_number(&self) -> u16 {
+        self.dice().len() as u16
     }
 
-    pub fn numbers(&self) -> &[u8] {
+    pub fn numbers(&self) -> &[u16] {
         &self.numbers
     }
 
-    pub fn sides(&self) -> &[u8] {
+    pub fn sides(&self) -> &[u16] {
         &self.sides
     }
 
-    pub fn sides_max(&self) -> u8 {
+    pub fn sides_max(&self) -> u16 {
         *self.sides.iter().max().unwrap_or(&0)
     }
 
-    pub fn total(&self) -> u16 {
+    pub fn is_fudge(&self) -> bool {
+        !self.dice.is_empty() && self.dice.iter().all(|die| die.kind == DieKind::Fudge)
+    }
+
+    pub fn is_percentile(&self) -> bool {
+        self.dice.iter().any(|die| die.kind == DieKind::Units)
+    }
+
+    // Every tens die read together with the units die, with 00 and 0 making 100
+    pub fn percentile_rolls(&self) -> Vec<u16> {
+        let units = self.dice.iter().find(|die| die.kind == DieKind::Units).map_or(0, |die| die.value());
+
+        self.dice.iter()
+            .filter(|die| die.kind == DieKind::Tens)
+            .map(|tens| match tens.value() + units {
+                0 => 100,
+                roll => roll as u16,
+            })
+            .collect()
+    }
+
+    pub fn total(&self) -> i64 {
         // For now, this just returns the sum. In the future it will decide whether to sum, count successes, something else...
         self.sum_sides()
     }
 
-    fn sum_sides(&self) -> u16 {
-        self.dice.iter().fold(0, |sum, die| sum + die.result as u16)
+    pub fn value(&self) -> RollValue {
+        // Without anything picking between them, percentile dice read as a plain d100 with the first tens die
+        if self.is_percentile() {
+            return RollValue::Decimal(self.percentile_rolls().first().copied().unwrap_or(0) as f64);
+        }
+        RollValue::Decimal(self.total() as f64)
+    }
+
+    fn sum_sides(&self) -> i64 {
+        self.dice.iter().map(|die| die.value()).sum()
     }
 
     pub fn add(&self, other: &Pool) -> Pool {
@@ -116,29 +169,41 @@ impl Pool {
         Pool::new_from_dice(&new_dice)
     }
 
-    pub fn count_dice_over(&self, target: u8) -> u8 {
-        self.dice.iter().filter(|d| d.equal_or_greater(target)).count() as u8
+    // Rolls one more die of the same kind for every die in the pool, for doubling dice on a critical hit
+    pub fn roll_again(&self, rng: &mut dyn RngCore) -> Result<Pool, RollError> {
+        if self.dice.len() * 2 > MAX_DICE { return Err(RollError::OverflowError); }
+
+        let extra: Vec<Die> = self.dice.iter().map(|die| die.explode(rng)).collect();
+
+        Ok(Pool::new_from_dice(&extra))
+    }
+
+    pub fn count_dice_over(&self, target: u16) -> u16 {
+        self.dice.iter().filter(|d| d.equal_or_greater(target)).count() as u16
     }
 
-    pub fn count_dice_under(&self, target: u8) -> u8 {
-        self.dice.iter().filter(|d| d.equal_or_less(target)).count() as u8
+    pub fn count_dice_under(&self, target: u16) -> u16 {
+        self.dice.iter().filter(|d| d.equal_or_less(target)).count() as u16
     }
 
-    pub fn count_successes(&self, tns: &[u8]) -> u16 {
-        self.dice.iter().fold(0, |sum, die| sum + die.count_successes(tns) as u16)
+    pub fn count_successes(&self, tns: &[u16]) -> u32 {
+        self.dice.iter().fold(0, |sum, die| sum + die.count_successes(tns) as u32)
     }
 
-    pub fn explode_n(&self, n: u8, recursive: bool) -> Result<Vec<Self>, RollError> {
+    pub fn explode_n(&self, n: u16, recursive: bool, rng: &mut dyn RngCore) -> Result<Vec<Self>, RollError> {
         if recursive && self.sides_max() == 1 { return Err(RollError::BlockedExplosionError); }
         let mut exploded_pools = vec![];
         exploded_pools.push(self.clone());
 
         let mut dice_to_explode = self.clone().dice;
+        let mut total_dice = dice_to_explode.len();
         while dice_to_explode.len() > 0 {
             let mut new_dice = vec![];
             for die in dice_to_explode.iter().filter(|d| d.equals(n)) {
-                new_dice.push(die.explode());
+                new_dice.push(die.explode(rng));
             }
+            total_dice += new_dice.len();
+            if total_dice > MAX_DICE { return Err(RollError::OverflowError); }
             exploded_pools.push(Pool::new_from_dice(&new_dice));
             if !recursive { break; }
             dice_to_explode = new_dice;
@@ -147,15 +212,15 @@ impl Pool {
         Ok(exploded_pools)
     }
 
-    pub fn explode_n_additive(&self, n: u8, recursive: bool) -> Result<Vec<Self>, RollError> {
-        let mut exploded_pools = self.explode_n(n, recursive)?;
+    pub fn explode_n_additive(&self, n: u16, recursive: bool, rng: &mut dyn RngCore) -> Result<Vec<Self>, RollError> {
+        let mut exploded_pools = self.explode_n(n, recursive, rng)?;
         let mut result_vector = exploded_pools.clone();
 
         while exploded_pools.len() >= 2 {
-            let mut explosions = exploded_pools.pop().unwrap_or(Pool::new(0, 0));
-            for die in exploded_pools.last_mut().unwrap_or(&mut Pool::new(0, 0)).dice.iter_mut().rev().filter(|d| d.equals(n)) {
-                let exploded_die = explosions.dice.pop().unwrap_or(Die { sides: 0, result: 0});
-                die.set(die.result + exploded_die.result);
+            let mut explosions = exploded_pools.pop().unwrap_or(Pool::empty());
+            for die in exploded_pools.last_mut().unwrap_or(&mut Pool::empty()).dice.iter_mut().rev().filter(|d| d.equals(n)) {
+                let exploded_die = explosions.dice.pop().unwrap_or(Die::new(0, 0));
+                die.set(die.result.checked_add(exploded_die.result).ok_or(RollError::OverflowError)?);
             }
         }
 
@@ -164,17 +229,20 @@ impl Pool {
         Ok(result_vector)
     }
 
-    pub fn explode_specific(&self, range: &[u8], recursive: bool) -> Result<Vec<Self>, RollError> {
+    pub fn explode_specific(&self, range: &[u16], recursive: bool, rng: &mut dyn RngCore) -> Result<Vec<Self>, RollError> {
         if recursive && range.len() > (self.sides_max() / 2).into() { return  Err(RollError::BlockedExplosionError); }
         let mut exploded_pools = vec![];
         exploded_pools.push(self.clone());
 
         let mut dice_to_explode = self.dice.clone();
+        let mut total_dice = dice_to_explode.len();
         while dice_to_explode.len() > 0 {
             let mut new_dice = vec![];
             for die in dice_to_explode.iter().filter(|d| d.is_in(range)) {
-                new_dice.push(die.explode());
+                new_dice.push(die.explode(rng));
             }
+            total_dice += new_dice.len();
+            if total_dice > MAX_DICE { return Err(RollError::OverflowError); }
             exploded_pools.push(Pool::new_from_dice(&new_dice));
             if !recursive { break; }
             dice_to_explode = new_dice;
@@ -183,15 +251,15 @@ impl Pool {
         Ok(exploded_pools)
     }
 
-    pub fn explode_specific_additive(&self, range: &[u8], recursive: bool) -> Result<Vec<Self>, RollError> {
-        let mut exploded_pools = self.explode_specific(range, recursive)?;
+    pub fn explode_specific_additive(&self, range: &[u16], recursive: bool, rng: &mut dyn RngCore) -> Result<Vec<Self>, RollError> {
+        let mut exploded_pools = self.explode_specific(range, recursive, rng)?;
         let mut result_vector = exploded_pools.clone();
 
         while exploded_pools.len() >= 2 {
-            let mut explosions = exploded_pools.pop().unwrap_or(Pool::new(0, 0));
-            for die in exploded_pools.last_mut().unwrap_or(&mut Pool::new(0, 0)).dice.iter_mut().rev().filter(|d| d.is_in(range)) {
-                let exploded_die = explosions.dice.pop().unwrap_or(Die { sides: 0, result: 0});
-                die.set(die.result + exploded_die.result);
+            let mut explosions = exploded_pools.pop().unwrap_or(Pool::empty());
+            for die in exploded_pools.last_mut().unwrap_or(&mut Pool::empty()).dice.iter_mut().rev().filter(|d| d.is_in(range)) {
+                let exploded_die = explosions.dice.pop().unwrap_or(Die::new(0, 0));
+                die.set(die.result.checked_add(exploded_die.result).ok_or(RollError::OverflowError)?);
             }
         }
 
@@ -200,7 +268,7 @@ impl Pool {
         Ok(result_vector)
     }
 
-    pub fn keep_exact(&self, range: &[u8]) -> Self {
+    pub fn keep_exact(&self, range: &[u16]) -> Self {
         let mut kept_dice = vec![];
         for die in self.dice.iter().filter(|d| d.is_in(range)) {
             kept_dice.push(*die);
@@ -209,112 +277,185 @@ impl Pool {
         Pool::new_from_dice(&kept_dice)
     }
 
-    pub fn keep_highest(&self, argument: u8) -> Self {
+    pub fn keep_highest(&self, argument: u16) -> Self {
         let mut dice_sorted = self.dice.clone();
         dice_sorted.sort_by(|d, e| d.result.cmp(&e.result));
 
-        let min_index = if argument > self.total_number() { 0 } else { (self.total_number() - argument) as usize };
+        let min_index = self.dice.len().saturating_sub(argument as usize);
 
         Pool::new_from_dice(&dice_sorted[min_index..])
     }
 
-    pub fn keep_lowest(&self, argument: u8) -> Self {
+    pub fn keep_lowest(&self, argument: u16) -> Self {
         let mut dice_sorted = self.dice.clone();
         dice_sorted.sort_by(|d, e| d.result.cmp(&e.result));
 
-        let max_index = if argument > self.total_number() { self.total_number() as usize } else { argument as usize };
+        let max_index = self.dice.len().min(argument as usize);
 
         Pool::new_from_dice(&dice_sorted[..max_index])
     }
 
+    // Gives back the dice that are left and the dice that were dropped. Among dice showing the same result,
+    // the ones rolled first are dropped first, and the dice that are left stay in the order they were rolled
+    pub fn drop_dice(&self, argument: u16, highest: bool) -> (Self, Self) {
+        let mut order: Vec<usize> = (0..self.dice.len()).collect();
+        match highest {
+            true => order.sort_by_key(|&i| Reverse(self.dice[i].result)),
+            false => order.sort_by_key(|&i| self.dice[i].result),
+        }
+        order.truncate(argument as usize);
+
+        let mut is_dropped = vec![false; self.dice.len()];
+        for &i in &order {
+            is_dropped[i] = true;
+        }
+        let kept: Vec<Die> = self.dice.iter().zip(&is_dropped).filter(|(_, &dropped)| !dropped).map(|(die, _)| *die).collect();
+        let dropped: Vec<Die> = order.iter().map(|&i| self.dice[i]).collect();
+
+        (Pool { dice: kept, ..self.clone() }, Pool { dice: dropped, ..self.clone() })
+    }
+
+    pub fn cap_max(&mut self, cap: u16) -> Pool {
+        let mut capped = vec![];
+        for die in self.dice.iter_mut().filter(|d| !d.equal_or_less(cap)) {
+            capped.push(*die);
+            die.set(cap);
+        }
+        Pool { dice: capped, ..self.clone() }
+    }
+
+    pub fn cap_min(&mut self, cap: u16) -> Pool {
+        let mut capped = vec![];
+        for die in self.dice.iter_mut().filter(|d| !d.equal_or_greater(cap)) {
+            capped.push(*die);
+            die.set(cap);
+        }
+        Pool { dice: capped, ..self.clone() }
+    }
+
+    // Caps each size of die in the pool separately, matching the caps to the pool's sides in order
+    pub fn cap_max_specific(&mut self, caps: &[u16]) -> Result<Pool, RollError> {
+        let caps = self.caps_by_sides(caps)?;
+        let mut capped = vec![];
+        for die in self.dice.iter_mut() {
+            let cap = caps(die.sides);
+            if !die.equal_or_less(cap) {
+                capped.push(*die);
+                die.set(cap);
+            }
+        }
+        Ok(Pool { dice: capped, ..self.clone() })
+    }
+
+    pub fn cap_min_specific(&mut self, caps: &[u16]) -> Result<Pool, RollError> {
+        let caps = self.caps_by_sides(caps)?;
+        let mut capped = vec![];
+        for die in self.dice.iter_mut() {
+            let cap = caps(die.sides);
+            if !die.equal_or_greater(cap) {
+                capped.push(*die);
+                die.set(cap);
+            }
+        }
+        Ok(Pool { dice: capped, ..self.clone() })
+    }
+
+    fn caps_by_sides(&self, caps: &[u16]) -> Result<impl Fn(u16) -> u16, RollError> {
+        if caps.len() != self.sides.len() { return Err(RollError::ArgumentError); }
+        let sides = self.sides.clone();
+        let caps = caps.to_vec();
+
+        Ok(move |die_sides| sides.iter().position(|&s| s == die_sides).map_or(die_sides, |i| caps[i]))
+    }
+
     #[allow(dead_code)]
-    pub fn reroll_all(&mut self) {
+    pub fn reroll_all(&mut self, rng: &mut dyn RngCore) {
         for die in self.dice.iter_mut() {
-            die.reroll();
+            die.reroll(rng);
         }
     }
 
-    pub fn reroll_n(&mut self, n: u8) -> Pool {
+    pub fn reroll_n(&mut self, n: u16, rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.equals(n)) {
-            die.reroll();
+            die.reroll(rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_n_better(&mut self, n: u8) -> Pool {
+    pub fn reroll_n_better(&mut self, n: u16, rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.equals(n)) {
-            new_rolls.push(die.reroll_better());
+            new_rolls.push(die.reroll_better(rng));
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_n_worse(&mut self, n: u8) -> Pool {
+    pub fn reroll_n_worse(&mut self, n: u16, rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.equals(n)) {
-            new_rolls.push(die.reroll_worse());
+            new_rolls.push(die.reroll_worse(rng));
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_n_recursive(&mut self, n: u8) -> Pool {
+    pub fn reroll_n_recursive(&mut self, n: u16, rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.equals(n)) {
-            die.reroll_excluding_single(n);
+            die.reroll_excluding_single(n, rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_specific(&mut self, range: &[u8]) -> Pool {
+    pub fn reroll_specific(&mut self, range: &[u16], rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.is_in(range)) {
-            die.reroll();
+            die.reroll(rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_specific_better(&mut self, range: &[u8]) -> Pool {
+    pub fn reroll_specific_better(&mut self, range: &[u16], rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.is_in(range)) {
-            die.reroll_better();
+            die.reroll_better(rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_specific_worse(&mut self, range: &[u8]) -> Pool {
+    pub fn reroll_specific_worse(&mut self, range: &[u16], rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.is_in(range)) {
-            die.reroll_worse();
+            die.reroll_worse(rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
-    pub fn reroll_specific_recursive(&mut self, range: &[u8]) -> Pool {
+    pub fn reroll_specific_recursive(&mut self, range: &[u16], rng: &mut dyn RngCore) -> Pool {
         let mut new_rolls = vec![];
         for die in self.dice.iter_mut().filter(|d| d.is_in(range)) {
-            die.reroll_excluding_range(range);
+            die.reroll_excluding_range(range, rng);
             new_rolls.push(*die);
         }
         Pool { dice: new_rolls, ..self.clone() }
     }
 
     #[allow(dead_code)]
-    fn reroll_n_or_less(&mut self, n: u8) {
+    fn reroll_n_or_less(&mut self, n: u16, rng: &mut dyn RngCore) {
         for die in self.dice.iter_mut().filter(|d| d.equal_or_less(n)) {
-            die.reroll();
+            die.reroll(rng);
         }
     }
 
     #[allow(dead_code)]
-    fn reroll_n_or_higher(&mut self, n: u8) {
+    fn reroll_n_or_higher(&mut self, n: u16, rng: &mut dyn RngCore) {
         for die in self.dice.iter_mut().filter(|d| d.equal_or_greater(n)) {
-            die.reroll();
+            die.reroll(rng);
         }
     }
 }
@@ -324,9 +465,9 @@ impl fmt::Display for Pool {
         match self.dice.len() {
             0 => write!(f, "[No dice]"),
             _ => {
-                let mut results = format!("{}", self.dice[0].result);
+                let mut results = format!("{}", self.dice[0]);
                 for i in 1..self.dice.len() {
-                    results = format!("{}, {}", results, self.dice[i].result)
+                    results = format!("{}, {}", results, self.dice[i])
                 }
                 write!(f, "[{}]", results)
             }
@@ -337,8 +478,73 @@ impl fmt::Display for Pool {
 impl FromStr for Pool {
     type Err = RollError;
 
-    fn from_str(_s: &str) -> Result<Self, Self::Err> {
-        // TODO: Actually implement this
-        Err(RollError::PlaceholderError)
+    // Literal pools of dice that were rolled by hand, written either as {3, 5, 6}d6 or {d6: 3, 5, 6}
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (results, sides) = if let Some((results, sides)) = s.trim().strip_prefix('{').and_then(|rest| rest.split_once("}d")) {
+            (results, sides)
+        } else if let Some((sides, results)) = s.trim().strip_prefix("{d").and_then(|rest| rest.strip_suffix('}')).and_then(|inner| inner.split_once(':')) {
+            (results, sides)
+        } else {
+            return Err(RollError::SymbolError(s.into()));
+        };
+
+        let sides: u16 = sides.trim().parse()?;
+        let mut dice = vec![];
+        for result_str in results.split_terminator(',') {
+            let result: u16 = result_str.trim().parse()?;
+            if result == 0 || result > sides { return Err(RollError::ArgumentError); }
+            dice.push(Die::new(sides, result));
+        }
+        if dice.is_empty() { return Err(RollError::MissingPoolError); }
+
+        Ok(Pool::new_from_dice(&dice))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_dice() {
+        let pool = Pool::new_from_dice(&[Die::new(6, 4), Die::new(6, 1), Die::new(6, 6), Die::new(6, 1), Die::new(6, 6)]);
+
+        let (kept, dropped) = pool.drop_dice(1, false);
+        assert_eq!(kept.dice(), &vec![Die::new(6, 4), Die::new(6, 6), Die::new(6, 1), Die::new(6, 6)]);
+        assert_eq!(dropped.dice(), &vec![Die::new(6, 1)]);
+        let (kept, dropped) = pool.drop_dice(1, true);
+        assert_eq!(kept.dice(), &vec![Die::new(6, 4), Die::new(6, 1), Die::new(6, 1), Die::new(6, 6)]);
+        assert_eq!(dropped.dice(), &vec![Die::new(6, 6)]);
+        assert_eq!(pool.drop_dice(7, false).0.dice().len(), 0);
+    }
+
+    #[test]
+    fn test_literal_pool() {
+        let expected = Pool::new_from_dice(&[Die::new(6, 3), Die::new(6, 5), Die::new(6, 6)]);
+
+        assert_eq!("{3,5,6}d6".parse::<Pool>().unwrap(), expected);
+        assert_eq!("{d6: 3, 5, 6}".parse::<Pool>().unwrap(), expected);
+        assert_eq!(expected.total(), 14);
+    }
+
+    #[test]
+    fn test_literal_pool_errors() {
+        assert!(matches!("{3,7}d6".parse::<Pool>(), Err(RollError::ArgumentError)));
+        assert!(matches!("{d10:0}".parse::<Pool>(), Err(RollError::ArgumentError)));
+        assert!(matches!("{}d6".parse::<Pool>(), Err(RollError::MissingPoolError)));
+        assert!("3d6".parse::<Pool>().is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let tens = |result| Die { sides: 10, result, kind: DieKind::Tens };
+        let units = |result| Die { sides: 10, result, kind: DieKind::Units };
+        let pool = Pool::new_from_dice(&[tens(3), tens(10), units(7)]);
+        assert!(pool.is_percentile());
+        assert_eq!(pool.percentile_rolls(), vec![37, 7]);
+        assert_eq!(pool.value(), RollValue::Decimal(37.0));
+
+        let hundred = Pool::new_from_dice(&[tens(10), units(10)]);
+        assert_eq!(hundred.percentile_rolls(), vec![100]);
     }
 }
@@ -1,4 +1,10 @@
 use chrono::prelude::*;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+    thread_rng,
+};
 use std::fmt;
 
 use super::{
@@ -16,19 +22,27 @@ pub struct Roll {
     result: RollValue,
     owner: String,
     timestamp: DateTime<Utc>,
+    seed: u64,
+    rng: StdRng,
 }
 
 impl Roll {
     pub fn new(expression: &str, comment: &str, roller: &str) -> Result<Self, RollError> {
+        Self::new_seeded(expression, comment, roller, thread_rng().gen())
+    }
+
+    // Rolling the same command with the same seed always gives the same dice, so any roll can be replayed from its seed
+    pub fn new_seeded(expression: &str, comment: &str, roller: &str, seed: u64) -> Result<Self, RollError> {
         let command = expression.to_string();
         let owner = roller.to_string();
         let comment = comment.to_string();
         let timestamp = Utc::now();
+        let mut rng = StdRng::seed_from_u64(seed);
 
-        let operations = RollStack::evaluate_string(expression)?;
+        let operations = RollStack::evaluate_string(expression, &mut rng)?;
         let result = operations.final_result.value()?;
 
-        Ok(Roll { command, comment, operations, result, owner, timestamp })
+        Ok(Roll { command, comment, operations, result, owner, timestamp, seed, rng })
     }
 
     pub fn revise(&self, expression: &str, comment: &str, reviser: &str) -> Result<Self, RollError> {
@@ -46,8 +60,10 @@ impl Roll {
 
         let new_timestamp = Utc::now();
 
+        // Revisions keep drawing from the original roll's RNG, so the revised roll can still be replayed from the same seed
+        let mut new_rng = self.rng.clone();
         let mut new_operations = self.operations.clone();
-        new_operations.append_from_string(expression)?;
+        new_operations.append_from_string(expression, &mut new_rng)?;
 
         let new_result = new_operations.final_result.value()?;
 
@@ -57,7 +73,9 @@ impl Roll {
             operations: new_operations,
             result: new_result,
             owner: reviser.to_string(),
-            timestamp: new_timestamp
+            timestamp: new_timestamp,
+            seed: self.seed,
+            rng: new_rng,
         };
 
         Ok(new_roll)
@@ -87,9 +105,17 @@ impl Roll {
         self.timestamp
     }
 
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn roll_again(&self) -> Result<Self, RollError> {
         Self::new(&self.command, &self.comment, &self.owner)
     }
+
+    pub fn replay(&self) -> Result<Self, RollError> {
+        Self::new_seeded(&self.command, &self.comment, &self.owner, self.seed)
+    }
 }
 
 impl fmt::Display for Roll {
@@ -111,3 +137,289 @@ impl fmt::Display for Roll {
         write!(f, "{}", breakdown)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::dice::{
+        command_translations::{fate, fate_ladder},
+        genesymbols::GeneSymbol,
+        value_kinds::{CocValue, OreValue, SuccessLevel},
+    };
+
+    #[test]
+    fn test_replay() {
+        let expressions = vec![
+            "4d6r1k3",
+            "(2d6kh1 & 3d8kl2)kh1",
+            "5d10ea10",
+            "8d10er10ro[1, 2]t8",
+        ];
+
+        for expression in expressions {
+            let roll = Roll::new(expression, "", "Tester").unwrap();
+            let replayed = roll.replay().unwrap();
+
+            assert_eq!(roll.operations(), replayed.operations());
+            assert_eq!(roll.result(), replayed.result());
+        }
+    }
+
+    #[test]
+    fn test_replay_revision() {
+        let roll = Roll::new_seeded("4d6", "", "Tester", 42).unwrap();
+        let revised = roll.revise("r1k3", "", "Tester").unwrap();
+        let replayed = Roll::new_seeded("4d6r1k3", "", "Tester", 42).unwrap();
+
+        assert_eq!(revised.seed(), 42);
+        assert_eq!(revised.operations(), replayed.operations());
+        assert_eq!(revised.result(), replayed.result());
+    }
+
+    #[test]
+    fn test_literal_pools() {
+        assert_eq!(Roll::new("{3, 5, 6}d6k2", "", "Tester").unwrap().result(), &RollValue::Decimal(11.0));
+        assert_eq!(Roll::new("{d10: 3, 7, 10}t7", "", "Tester").unwrap().result(), &RollValue::Successes(2));
+        assert_eq!(Roll::new("{2, 4}d6 + 1d1", "", "Tester").unwrap().result(), &RollValue::Decimal(7.0));
+        assert!(matches!(Roll::new("{d8: 1, 5, 8}ga", "", "Tester").unwrap().result(), RollValue::Genesys(_)));
+        assert!(Roll::new("{3, 9}d6", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_fudge() {
+        let roll = Roll::new_seeded("4df", "", "Tester", 2).unwrap();
+        assert_eq!(roll.result(), &RollValue::Decimal(-2.0));
+        assert_eq!(format!("{}", roll), "4dF -> [-, -, +, -]");
+        let skilled = Roll::new_seeded(&fate("+3").unwrap(), "", "Tester", 2).unwrap();
+        assert_eq!(skilled.result(), &RollValue::Decimal(1.0));
+        assert_eq!(fate_ladder(skilled.result().clone().to_decimal().unwrap()), "Average");
+        let unsigned = Roll::new_seeded(&fate("5").unwrap(), "", "Tester", 2).unwrap();
+        assert_eq!(fate_ladder(unsigned.result().clone().to_decimal().unwrap()), "Good");
+        assert!(Roll::new("4df + 3", "", "Tester").is_ok());
+        assert!(Roll::new("fd6", "", "Tester").is_err());
+        assert!(Roll::new("4dft5", "", "Tester").is_ok());
+        assert!(Roll::new("4dfkf", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_shadowrun() {
+        assert_eq!(Roll::new("{1, 5, 6, 6, 2}d6sr0", "", "Tester").unwrap().result(), &RollValue::Successes(3));
+        assert_eq!(Roll::new("{1, 5, 6, 6, 2}d6sr2", "", "Tester").unwrap().result(), &RollValue::Successes(2));
+
+        let glitch = Roll::new("{1, 1, 1, 6}d6sr0", "", "Tester").unwrap();
+        assert!(format!("{}", glitch).ends_with("1 hit(s), glitch!"));
+        let critical = Roll::new("{1, 1, 1, 3}d6sr0", "", "Tester").unwrap();
+        assert!(format!("{}", critical).ends_with("0 hit(s), critical glitch!"));
+        let clean = Roll::new("{1, 1, 3, 6}d6sr0", "", "Tester").unwrap();
+        assert!(format!("{}", clean).ends_with("1 hit(s)"));
+        assert!(Roll::new("6d6sr[1, 2]", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_l5r() {
+        assert_eq!(Roll::new("{10, 3, 7, 10}d10k2", "", "Tester").unwrap().result(), &RollValue::Decimal(20.0));
+        assert!(Roll::new("6d10ro1ea10k3+5", "", "Tester").is_ok());
+
+        let narrative = Roll::new("{d6: 1, 4, 6}lr & {d12: 10}ls", "", "Tester").unwrap();
+        assert_eq!(format!("{}", narrative.result()), "1 Explosive Success, 2 Successes, 1 Opportunity, 2 Strife");
+        assert!(Roll::new("3d6er6lr&2d12er[11, 12]ls", "", "Tester").is_ok());
+    }
+
+    #[test]
+    fn test_force_dice() {
+        let force = Roll::new("{d12: 7, 10}gf", "", "Tester").unwrap();
+        assert_eq!(format!("{}", force.result()), "Force: 2 Light Side, 2 Dark Side");
+        let mixed = Roll::new("{d12: 8}gf & {d8: 2}ga & {d8: 2}gd", "", "Tester").unwrap();
+        assert_eq!(format!("{}", mixed.result()), "Force: 1 Light Side");
+        let with_success = Roll::new("{d12: 1}gf & {d8: 2}ga", "", "Tester").unwrap();
+        assert_eq!(format!("{}", with_success.result()), "1 Success | Force: 1 Dark Side");
+    }
+
+    #[test]
+    fn test_crit() {
+        let crit = Roll::new_seeded("{2, 3}d6crit+3", "", "Tester", 0).unwrap();
+        assert_eq!(crit.result(), &RollValue::Decimal(18.0));
+        let doubled = crit.operations().iter().filter_map(|op| op.clone().pool().ok()).next_back().unwrap();
+        assert_eq!(doubled.dice().iter().map(|die| die.result).collect::<Vec<_>>(), vec![2, 3, 5, 5]);
+        assert!(Roll::new("2d6crit+1d8crit+3", "", "Tester").is_ok());
+    }
+
+    #[test]
+    fn test_pbta() {
+        let strong = Roll::new("({4, 5}d6+1)pbta[7, 10]", "", "Tester").unwrap();
+        assert_eq!(strong.result(), &RollValue::Decimal(10.0));
+        assert!(format!("{}", strong).ends_with("10 -> strong hit"));
+        let weak = Roll::new("({1, 5, 6}d6kl2+1)pbta[7, 10]", "", "Tester").unwrap();
+        assert!(format!("{}", weak).ends_with("7 -> weak hit"));
+        let miss = Roll::new("({2, 3}d6-1)pbta[7, 10]", "", "Tester").unwrap();
+        assert!(format!("{}", miss).ends_with("4 -> miss"));
+        assert!(Roll::new("({2, 3}d6)pbta[7, 10] + 2", "", "Tester").is_ok());
+        assert!(Roll::new("2d6pbta[10, 7]", "", "Tester").is_err());
+        assert!(Roll::new("2d6pbta7", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_swade() {
+        let roll = Roll::new("({d8: 3} & {d6: 5})kh1+1", "", "Tester").unwrap();
+        assert_eq!(roll.result(), &RollValue::Decimal(6.0));
+        let aced = Roll::new_seeded("(1d8ea8 & 1d6ea6)kh1", "", "Tester", 16).unwrap();
+        assert_eq!(aced.result(), &RollValue::Decimal(15.0));
+        assert_eq!(format!("{}", aced), "1d8 -> [8], explode additive 8 -> [15]; 1d6 -> [3], explode additive 6 -> [3], keep highest 1 -> [15]");
+        let dice: Vec<_> = aced.operations().iter().filter_map(|op| op.clone().pool().ok()).collect();
+        assert_eq!(dice.last().unwrap().total_number(), 1);
+        assert!(dice.iter().any(|pool| pool.sides_max() == 8) && dice.iter().any(|pool| pool.sides_max() == 6));
+        let wild = Roll::new_seeded("(1d8ea8 & 1d6ea6)kh1", "", "Tester", 30).unwrap();
+        assert_eq!(wild.result(), &RollValue::Decimal(13.0));
+        assert!(format!("{}", wild).ends_with("1d6 -> [6], explode additive 6 -> [13], keep highest 1 -> [13]"));
+    }
+
+    #[test]
+    fn test_year_zero_push() {
+        let roll = Roll::new("({6, 1, 3}d6 & {2, 5}d6 & {1}d6)t6", "", "Tester").unwrap();
+        assert_eq!(roll.result(), &RollValue::Successes(1));
+        let pushed = roll.revise("ro[2, 3, 4, 5]t6", "", "Tester").unwrap();
+        let dice = pushed.operations().last().unwrap().clone().pool().unwrap();
+        assert_eq!(dice.total_number(), 6);
+        assert_eq!((dice.dice()[0].result, dice.dice()[1].result, dice.dice()[5].result), (6, 1, 1));
+        assert!(Roll::new("(0d6 & 2d6 & 0d6)t6", "", "Tester").is_ok());
+    }
+
+    #[test]
+    fn test_cortex() {
+        let total = Roll::new("({d4: 1} & {d8: 7, 3} & {d10: 5})ct2", "", "Tester").unwrap();
+        assert_eq!(total.result(), &RollValue::Decimal(12.0));
+        assert!(format!("{}", total).ends_with("total [7, 5], effect die d8, 1 hitch(es)"));
+        let effect = Roll::new("({d4: 1} & {d8: 7, 3} & {d10: 5})ce2", "", "Tester").unwrap();
+        assert_eq!(effect.result(), &RollValue::Decimal(10.0));
+        assert!(format!("{}", effect).ends_with("effect die d10, 1 hitch(es)"));
+        let botch = Roll::new("({d6: 1} & {d8: 1})ct2", "", "Tester").unwrap();
+        assert!(format!("{}", botch).ends_with("effect die d4, botch!"));
+        assert!(Roll::new("[1, 2]d[6, 10]ct2", "", "Tester").is_ok());
+    }
+
+    #[test]
+    fn test_coc() {
+        for (expression, penalty) in [("1dpcoc50", false), ("3dpcoc50", false), ("3dpcocp50", true)] {
+            let roll = Roll::new(expression, "", "Tester").unwrap();
+            let rolls = roll.operations()[0].clone().pool().unwrap().percentile_rolls();
+            let picked = match penalty {
+                false => *rolls.iter().min().unwrap(),
+                true => *rolls.iter().max().unwrap(),
+            };
+            assert_eq!(roll.result(), &RollValue::Coc(CocValue::new(picked, 50)));
+        }
+        assert!((1.0..=100.0).contains(&Roll::new("1dp", "", "Tester").unwrap().result().clone().to_decimal().unwrap()));
+        assert!(Roll::new("1d100coc50", "", "Tester").is_err());
+        assert!(Roll::new("1dpcoc[50, 60]", "", "Tester").is_err());
+
+        assert_eq!(SuccessLevel::coc(1, 10), SuccessLevel::Critical);
+        assert_eq!(SuccessLevel::coc(10, 50), SuccessLevel::Extreme);
+        assert_eq!(SuccessLevel::coc(25, 50), SuccessLevel::Hard);
+        assert_eq!(SuccessLevel::coc(50, 50), SuccessLevel::Regular);
+        assert_eq!(SuccessLevel::coc(51, 50), SuccessLevel::Failure);
+        assert_eq!(SuccessLevel::coc(97, 50), SuccessLevel::Failure);
+        assert_eq!(SuccessLevel::coc(97, 45), SuccessLevel::Fumble);
+        assert_eq!(SuccessLevel::coc(100, 99), SuccessLevel::Fumble);
+    }
+
+    #[test]
+    fn test_wfrp() {
+        let success = Roll::new("{d100: 34}wf45", "", "Tester").unwrap();
+        assert_eq!(success.result(), &RollValue::Successes(1));
+        assert!(format!("{}", success).ends_with("34 vs 45 -> SL +1"));
+        assert!(format!("{}", Roll::new("{d100: 47}wf45", "", "Tester").unwrap()).ends_with("SL -0"));
+        assert!(format!("{}", Roll::new("{d100: 4}wf2", "", "Tester").unwrap()).ends_with("SL +0"));
+        assert!(format!("{}", Roll::new("{d100: 97}wf99", "", "Tester").unwrap()).ends_with("SL -0"));
+        assert!(format!("{}", Roll::new("{d100: 33}wf50", "", "Tester").unwrap()).ends_with("SL +2, critical!"));
+        assert!(format!("{}", Roll::new("{d100: 100}wf50", "", "Tester").unwrap()).ends_with("SL -5, fumble!"));
+
+        let opposed = Roll::new("{d100: 34}wf45 - {d100: 60}wf38", "", "Tester").unwrap();
+        assert_eq!(opposed.result(), &RollValue::Decimal(4.0));
+        assert!(Roll::new("1dpwf45", "", "Tester").is_ok());
+        assert!(Roll::new("1d100wf[40, 50]", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_wod_botch() {
+        let botch = Roll::new("{2, 1, 5}d10t6b1", "", "Tester").unwrap();
+        assert_eq!(botch.result(), &RollValue::Successes(-1));
+        assert!(format!("{}", botch).ends_with("1 or lower -> -1 success(es), botch!"));
+        let cancelled = Roll::new("{7, 1, 1}d10t6b1", "", "Tester").unwrap();
+        assert_eq!(cancelled.result(), &RollValue::Successes(-1));
+        assert!(!format!("{}", cancelled).ends_with("botch!"));
+        let specialty = Roll::new("{10, 6, 1}d10t[1, 1, 1, 1, 2]b1", "", "Tester").unwrap();
+        assert_eq!(specialty.result(), &RollValue::Successes(2));
+    }
+
+    #[test]
+    fn test_ironsworn() {
+        let action = Roll::new("({d6: 4} & {d10: 3, 9})is3", "", "Tester").unwrap();
+        assert_eq!(action.result(), &RollValue::Decimal(7.0));
+        assert!(format!("{}", action).ends_with("action 7 vs [3, 9] -> weak hit"));
+        let capped = Roll::new("({d6: 6} & {d10: 10, 10})is5", "", "Tester").unwrap();
+        assert!(format!("{}", capped).ends_with("action 10 vs [10, 10] -> miss, match!"));
+        let burned = action.revise("ism10", "", "Tester").unwrap();
+        assert!(format!("{}", burned).ends_with("momentum 10 vs [3, 9] -> strong hit"));
+        let progress = Roll::new("{d10: 5, 5}isp6", "", "Tester").unwrap();
+        assert!(format!("{}", progress).ends_with("progress 6 vs [5, 5] -> strong hit, match!"));
+        assert!(Roll::new("2d10is3", "", "Tester").is_err());
+        assert!(Roll::new("1d10isp3", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_ore() {
+        let roll = Roll::new("{4, 7, 1, 7, 4, 7}d10ore0", "", "Tester").unwrap();
+        assert_eq!(roll.result(), &RollValue::Ore(OreValue { sets: vec![(3, 7), (2, 4)], waste: vec![1], wiggle: 0 }));
+        assert!(format!("{}", roll).ends_with("sets 3x7, 2x4"));
+        let hard = Roll::new("{4, 7, 10, 3}d10ore[1, 2]", "", "Tester").unwrap();
+        assert_eq!(format!("{}", hard.result()), "2x10 + 2 wiggle dice");
+        let tied = Roll::new("{2, 9, 2, 9, 5}d10ore0", "", "Tester").unwrap();
+        assert_eq!(format!("{}", tied.result()), "2x9, 2x2");
+        assert_eq!(format!("{}", Roll::new("{1, 2, 3}d10ore0", "", "Tester").unwrap().result()), "No sets");
+        assert!(Roll::new("6d10ore0 + 1", "", "Tester").is_err());
+        assert!(Roll::new("6d10ore[1, 2, 3]", "", "Tester").is_err());
+    }
+
+    #[test]
+    fn test_genesys_summary() {
+        let roll = Roll::new("{d8: 4, 7}ga & {d8: 2}gd & {d12: 12}gp", "", "Tester").unwrap();
+        let gen_val = match roll.result() {
+            RollValue::Genesys(gen_val) => gen_val,
+            _ => panic!("Not a Genesys result"),
+        };
+        assert_eq!(format!("{}", gen_val), "2 Successes, 1 Advantage, 1 Triumph");
+        assert_eq!(gen_val.summary(&HashMap::new()), "Succeeded with 1 Advantage | Triumph!");
+
+        let emoji = HashMap::from([(GeneSymbol::Failure, "❌".to_string()), (GeneSymbol::Threat, "🔽".to_string())]);
+        let failed = Roll::new("{d8: 7}gd & {d12: 1}gf", "", "Tester").unwrap();
+        match failed.result() {
+            RollValue::Genesys(gen_val) => assert_eq!(gen_val.summary(&emoji), "❌ Failed with 🔽 2 Threats | 1 Dark Side"),
+            _ => panic!("Not a Genesys result"),
+        }
+    }
+
+    #[test]
+    fn test_large_pools() {
+        let roll = Roll::new_seeded("300d6", "", "Tester", 7).unwrap();
+        assert_eq!(roll.operations()[0].clone().pool().unwrap().total_number(), 300);
+        assert!(matches!(roll.result(), RollValue::Decimal(total) if (300.0..=1800.0).contains(total)));
+
+        let roll = Roll::new_seeded("1d1000", "", "Tester", 7).unwrap();
+        assert!(matches!(roll.result(), RollValue::Decimal(total) if (1.0..=1000.0).contains(total)));
+
+        let roll = Roll::new_seeded("(100+200)d6t4", "", "Tester", 7).unwrap();
+        assert!(matches!(roll.result(), RollValue::Successes(sux) if (0..=300).contains(sux)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(matches!(Roll::new("70000d6", "", "Tester"), Err(RollError::OverflowError)));
+        assert!(matches!(Roll::new("1d70000", "", "Tester"), Err(RollError::OverflowError)));
+        assert!(matches!(Roll::new("[40000, 40000]d6", "", "Tester"), Err(RollError::OverflowError)));
+        assert!(matches!(Roll::new("(1000d1000)d6", "", "Tester"), Err(RollError::OverflowError)));
+        assert!(matches!(Roll::new("40000d6 & 40000d6", "", "Tester"), Err(RollError::OverflowError)));
+        assert!(matches!(Roll::new("65535d1er1", "", "Tester"), Err(RollError::BlockedExplosionError)));
+        assert!(matches!(Roll::new("65535d2e2", "", "Tester"), Err(RollError::OverflowError)));
+    }
+}
@@ -1,7 +1,9 @@
+use rand::RngCore;
 use crate::math::{rpn_token::RpnToken, math_errors::MathError};
 use super::{
     dice_errors::RollError,
     roll_token::RollToken,
+    token_kinds::Dice,
 };
 
 #[derive(Clone, Debug)]
@@ -11,21 +13,21 @@ pub struct RollStack {
 }
 
 impl RollStack {
-    pub fn evaluate_string(infix_expression: &str) -> Result<Self, RollError> {
+    pub fn evaluate_string(infix_expression: &str, rng: &mut dyn RngCore) -> Result<Self, RollError> {
         let infix_tokens = RollToken::tokenize_expression(infix_expression)?;
 
-        Self::evaluate_tokens(&infix_tokens)
+        Self::evaluate_tokens(&infix_tokens, rng)
     }
 
-    pub fn evaluate_tokens(infix_tokens: &[RollToken]) -> Result<Self, RollError> {
+    pub fn evaluate_tokens(infix_tokens: &[RollToken], rng: &mut dyn RngCore) -> Result<Self, RollError> {
         let postfix_tokens = RollToken::shunting_dice(&infix_tokens)?;
 
-        let (operations, final_result) = Self::resolve_rpn(&postfix_tokens, &[])?;
+        let (operations, final_result) = Self::resolve_rpn(&postfix_tokens, &[], rng)?;
 
         return Ok(RollStack { operations, final_result })
     }
 
-    pub fn resolve_rpn(postfix_tokens: &[RollToken], starting_stack: &[RollToken]) -> Result<(Vec<RollToken>, RollToken), RollError> {
+    pub fn resolve_rpn(postfix_tokens: &[RollToken], starting_stack: &[RollToken], rng: &mut dyn RngCore) -> Result<(Vec<RollToken>, RollToken), RollError> {
         let tokens = postfix_tokens.to_vec();
         let mut stack = starting_stack.to_vec();
         let mut operations = vec![];
@@ -48,23 +50,28 @@ impl RollStack {
                     }
                 },
                 RollToken::Argument(_) => stack.push(token),
+                RollToken::Dice(Dice { pool: Some(_) }) => {
+                    // Literal pools come in already rolled
+                    operations.push(token.clone());
+                    stack.push(token);
+                },
                 RollToken::Dice(dice) => {
                     let right = stack.pop().ok_or(MathError::OperatorMismatchError)?;
                     let left = stack.pop().ok_or(MathError::OperatorMismatchError)?;
-                    let dice_resolved = dice.apply(left.argument()?, right.argument()?)?;
+                    let dice_resolved = dice.apply(left.argument()?, right.argument()?, rng)?;
                     operations.push(RollToken::Dice(dice_resolved.clone()));
                     stack.push(RollToken::Dice(dice_resolved));
                 },
                 RollToken::Operator(operator) => {
                     let right = stack.pop().ok_or(MathError::OperatorMismatchError)?;
                     let left = stack.pop().ok_or(MathError::OperatorMismatchError)?;
-                    let operator_resolved = operator.apply(left, right.argument()?)?;
+                    let operator_resolved = operator.apply(left, right.argument()?, rng)?;
                     operations.push(RollToken::Operator(operator_resolved.clone()));
                     stack.push(RollToken::Operator(operator_resolved));
                 },
                 RollToken::Conversion(conversion) => {
                     let token = stack.pop().ok_or(MathError::OperatorMismatchError)?;
-                    let conversion_resolved = conversion.apply(token)?;
+                    let conversion_resolved = conversion.apply(token, rng)?;
                     operations.push(RollToken::Conversion(conversion_resolved.clone()));
                     stack.push(RollToken::Conversion(conversion_resolved));
                 },
@@ -87,12 +94,12 @@ impl RollStack {
         }
     }
 
-    pub fn append_from_string(&mut self, infix_expression: &str) -> Result<(), RollError> {
+    pub fn append_from_string(&mut self, infix_expression: &str, rng: &mut dyn RngCore) -> Result<(), RollError> {
         let infix_tokens = RollToken::tokenize_expression(infix_expression)?;
         let postfix_tokens = RollToken::shunting_dice(&infix_tokens)?;
 
         let previous_result = self.final_result.clone();
-        let (mut new_operations, new_result) = Self::resolve_rpn(&postfix_tokens, &[previous_result])?;
+        let (mut new_operations, new_result) = Self::resolve_rpn(&postfix_tokens, &[previous_result], rng)?;
 
         self.operations.append(&mut new_operations);
         self.final_result = new_result;
@@ -62,6 +62,7 @@ impl RollToken {
             },
             RollToken::Argument(argument) => match argument {
                 Argument::Array(_) => Err(RollError::NotImplementedError),
+                Argument::Fudge | Argument::Percentile => Err(RollError::NotANumberError),
                 Argument::Single(value) => {
                     let v = *value;
                     Ok(v.into())
@@ -77,8 +78,9 @@ impl RollToken {
     pub fn argument(self) -> Result<Argument, RollError> {
         match self {
             RollToken::Argument(argument) => Ok(argument),
-            RollToken::Dice(dice) => Ok(Argument::Single(dice.value()?.to_decimal()? as u8)),
-            RollToken::Operator(operator) => Ok(Argument::Single(operator.value()?.to_decimal()? as u8)),
+            RollToken::Dice(dice) => Argument::from_decimal(dice.value()?.to_decimal()?),
+            RollToken::Operator(operator) => Argument::from_decimal(operator.value()?.to_decimal()?),
+            RollToken::Math(RpnToken::Number(number)) if number > u16::MAX as f64 => Err(RollError::OverflowError),
             _ => Err(RollError::ArgumentError)
         }
     }
@@ -135,14 +137,14 @@ impl RollToken {
                                         break;
                                     }
                                 },
-                                RollToken::Dice(_) | RollToken::Operator(_) => postfix_queue.push(token_stack.pop().ok_or(MathError::ImpossibleError)?),
+                                RollToken::Dice(_) | RollToken::Operator(_) | RollToken::Conversion(_) => postfix_queue.push(token_stack.pop().ok_or(MathError::ImpossibleError)?),
                                 _ => break
                             }
                         }
                         token_stack.push(token);
                     },
                 },
-                RollToken::Argument(_) => postfix_queue.push(token),
+                RollToken::Argument(_) | RollToken::Dice(Dice { pool: Some(_) }) => postfix_queue.push(token),
                 RollToken::Dice(_) => {
                     while let Some(RollToken::Dice(_)) = token_stack.last() {
                         postfix_queue.push(token_stack.pop().ok_or(MathError::ImpossibleError)?);
@@ -176,7 +178,7 @@ impl From<RpnToken> for RollToken {
     fn from(rpn_token: RpnToken) -> Self {
         match rpn_token {
             RpnToken::Number(number) => {
-                let number_as_argument = number as u8;
+                let number_as_argument = number as u16;
                 if number == number_as_argument as f64 {
                     RollToken::Argument(Argument::Single(number_as_argument))
                 } else {
@@ -199,6 +201,8 @@ impl TryFrom<RollToken> for RpnToken {
             RollToken::Conversion(conversion)   => Ok(RpnToken::Number(conversion.value().or(Err(MathError::PlaceholderError))?.to_decimal().or(Err(MathError::PlaceholderError))?)),
             RollToken::Argument(argument)   => match argument {
                 Argument::Array(_)                => Err(MathError::PlaceholderError),
+                Argument::Fudge                   => Err(MathError::PlaceholderError),
+                Argument::Percentile              => Err(MathError::PlaceholderError),
                 Argument::Single(number)      => Ok(RpnToken::Number(number.into()))
             },
             RollToken::Combination(combination) => Ok(RpnToken::Number(combination.value().or(Err(MathError::PlaceholderError))?.to_decimal().or(Err(MathError::PlaceholderError))?))
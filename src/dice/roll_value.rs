@@ -7,8 +7,11 @@ use std::fmt;
 #[derive(Clone, Debug, PartialEq)]
 pub enum RollValue {
     Decimal(f64),
-    Successes(i16),
+    Successes(i32),
     Genesys(GenesysValue),
+    L5r(L5rValue),
+    Coc(CocValue),
+    Ore(OreValue),
 }
 
 impl RollValue {
@@ -36,6 +39,11 @@ impl RollValue {
                 RollValue::Genesys(right) => Ok(RollValue::Genesys(left.add(right))),
                 _ => Err(RollError::NotANumberError),
             },
+            RollValue::L5r(left) => match other {
+                RollValue::L5r(right) => Ok(RollValue::L5r(left.add(right))),
+                _ => Err(RollError::NotANumberError),
+            },
+            RollValue::Coc(_) | RollValue::Ore(_) => Err(RollError::NotANumberError),
         }
     }
 }
@@ -46,6 +54,9 @@ impl fmt::Display for RollValue {
             RollValue::Decimal(number) => write!(f, "{}", number),
             RollValue::Successes(sux) => write!(f, "{}", sux),
             RollValue::Genesys(gen_val) => write!(f, "{}", gen_val),
+            RollValue::L5r(l5r_val) => write!(f, "{}", l5r_val),
+            RollValue::Coc(coc_val) => write!(f, "{}", coc_val),
+            RollValue::Ore(ore_val) => write!(f, "{}", ore_val),
         }
     }
 }
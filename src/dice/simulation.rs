@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+use rand::RngCore;
+use super::{
+    dice_errors::RollError,
+    distribution::Distribution,
+    odds,
+    roll_stack::RollStack,
+    roll_token::RollToken,
+    roll_value::RollValue,
+};
+
+// z-score for a 95% confidence interval
+const CONFIDENCE_Z: f64 = 1.96;
+// Asking for more rolls than this gets this many instead, so the results can't outgrow memory
+pub const MAX_TRIALS: usize = 1_000_000;
+
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    results: Vec<f64>,
+    requested: usize,
+}
+
+impl Simulation {
+    // Rolls the expression up to the requested number of times, stopping early if the time budget runs out.
+    // At least one roll is always made, so a broken expression still reports its error.
+    pub fn run(infix_expression: &str, trials: usize, budget: Duration, rng: &mut dyn RngCore) -> Result<Self, RollError> {
+        let trials = trials.min(MAX_TRIALS);
+        let infix_tokens = RollToken::tokenize_expression(infix_expression)?;
+        let postfix_tokens = RollToken::shunting_dice(&infix_tokens)?;
+
+        let start = Instant::now();
+        let mut results = Vec::with_capacity(trials);
+        while results.is_empty() || (results.len() < trials && start.elapsed() < budget) {
+            let (_, final_result) = RollStack::resolve_rpn(&postfix_tokens, &[], rng)?;
+            results.push(numeric_value(final_result.value()?));
+        }
+        results.sort_by(|a, b| a.total_cmp(b));
+
+        Ok(Simulation { results, requested: trials })
+    }
+
+    pub fn trials(&self) -> usize {
+        self.results.len()
+    }
+
+    // Whether the time budget ran out before all the requested rolls were made
+    pub fn cut_short(&self) -> bool {
+        self.results.len() < self.requested
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.results.iter().sum::<f64>() / self.trials() as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.results.iter().map(|result| (result - mean).powi(2)).sum::<f64>() / self.trials() as f64;
+
+        variance.sqrt()
+    }
+
+    // 95% confidence interval for the true mean of the roll
+    pub fn mean_interval(&self) -> (f64, f64) {
+        let margin = CONFIDENCE_Z * self.std_dev() / (self.trials() as f64).sqrt();
+
+        (self.mean() - margin, self.mean() + margin)
+    }
+
+    pub fn min(&self) -> f64 {
+        self.results[0]
+    }
+
+    pub fn max(&self) -> f64 {
+        self.results[self.trials() - 1]
+    }
+
+    // Nearest-rank percentile, taking a percentage from 0 to 100
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let rank = (percent / 100.0 * self.trials() as f64).ceil() as usize;
+
+        self.results[rank.clamp(1, self.trials()) - 1]
+    }
+
+    pub fn p_at_least(&self, threshold: f64) -> f64 {
+        self.results.iter().filter(|&&result| result >= threshold).count() as f64 / self.trials() as f64
+    }
+
+    // 95% confidence interval for the chance of rolling at least the threshold (Wilson score interval)
+    pub fn p_at_least_interval(&self, threshold: f64) -> (f64, f64) {
+        let n = self.trials() as f64;
+        let p = self.p_at_least(threshold);
+        let z2 = CONFIDENCE_Z.powi(2);
+        let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+
+        ((centre - margin).max(0.0), (centre + margin).min(1.0))
+    }
+
+    // Counts of results falling in each of (at most) the given number of equally wide bins, as (low, high, count).
+    // Rolls with few possible results get one bin per result instead.
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, f64, usize)> {
+        let mut distinct = self.results.clone();
+        distinct.dedup();
+        if distinct.len() <= bins {
+            return distinct.iter().map(|&value| (value, value, self.results.iter().filter(|&&result| result == value).count())).collect();
+        }
+
+        let width = (self.max() - self.min()) / bins as f64;
+        let mut histogram: Vec<(f64, f64, usize)> = (0..bins).map(|i| (self.min() + width * i as f64, self.min() + width * (i + 1) as f64, 0)).collect();
+        for result in &self.results {
+            let bin = (((result - self.min()) / width) as usize).min(bins - 1);
+            histogram[bin].2 += 1;
+        }
+
+        histogram
+    }
+
+    pub fn to_distribution(&self) -> Distribution {
+        Distribution::new(self.results.iter().map(|&result| (result, 1.0 / self.trials() as f64)).collect())
+    }
+}
+
+// Works out the exact odds where it can, and falls back on simulating the roll when there are too many possibilities or
+// operators the exact engine doesn't understand. The number of trials is returned when the result is an estimate.
+pub fn exact_or_simulated(infix_expression: &str, trials: usize, budget: Duration, rng: &mut dyn RngCore) -> Result<(Distribution, Option<usize>), RollError> {
+    match odds::evaluate_string(infix_expression) {
+        Err(RollError::IntractableError) => Simulation::run(infix_expression, trials, budget, rng)
+            .map(|simulation| (simulation.to_distribution(), Some(simulation.trials()))),
+        other => other.map(|distribution| (distribution, None)),
+    }
+}
+
+// Genesys and L5R results don't have a single number to them, so they're counted by (net) successes, and ORE sets by the widest one
+fn numeric_value(value: RollValue) -> f64 {
+    match value {
+        RollValue::Decimal(number) => number,
+        RollValue::Successes(sux) => sux as f64,
+        RollValue::Genesys(gen_val) => gen_val.net_successes() as f64,
+        RollValue::L5r(l5r_val) => l5r_val.successes() as f64,
+        RollValue::Coc(coc_val) => coc_val.level.rank() as f64,
+        RollValue::Ore(ore_val) => ore_val.widest() as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const BUDGET: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_simulation() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let simulation = Simulation::run("2d6", 20000, BUDGET, &mut rng).unwrap();
+        assert_eq!(simulation.trials(), 20000);
+        assert!(!simulation.cut_short());
+        assert!((simulation.mean() - 7.0).abs() < 0.1);
+        assert!((simulation.p_at_least(7.0) - 21.0 / 36.0).abs() < 0.02);
+        let (low, high) = simulation.mean_interval();
+        assert!(low < simulation.mean() && simulation.mean() < high);
+        assert_eq!(simulation.min(), 2.0);
+        assert_eq!(simulation.max(), 12.0);
+        assert_eq!(simulation.percentile(50.0), 7.0);
+        assert_eq!(simulation.histogram(12).len(), 11);
+        assert_eq!(simulation.histogram(5).iter().map(|(_, _, count)| count).sum::<usize>(), 20000);
+    }
+
+    #[test]
+    fn test_simulation_budget() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let simulation = Simulation::run("10d10er10t8", 1_000_000, Duration::ZERO, &mut rng).unwrap();
+        assert_eq!(simulation.trials(), 1);
+        assert!(simulation.cut_short());
+        let huge = Simulation::run("1d6", usize::MAX, Duration::ZERO, &mut rng).unwrap();
+        assert_eq!(huge.trials(), 1);
+    }
+
+    #[test]
+    fn test_simulation_genesys() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let simulation = Simulation::run("2d8ga&1d8gd", 1000, BUDGET, &mut rng).unwrap();
+        assert!(simulation.min() >= -2.0);
+        assert!(simulation.max() <= 4.0);
+        assert!(Simulation::run("2d8ga+", 1000, BUDGET, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_exact_or_simulated() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let (distribution, simulated) = exact_or_simulated("2d6", 100, BUDGET, &mut rng).unwrap();
+        assert_eq!(simulated, None);
+        assert_eq!(distribution.max(), 12.0);
+
+        // Too many distinct totals for one group of dice, and too many pairs of totals to add together
+        for expression in ["2d700kh2", "1d5000+1d5000"] {
+            assert!(matches!(odds::evaluate_string(expression), Err(RollError::IntractableError)));
+            let (_, simulated) = exact_or_simulated(expression, 100, BUDGET, &mut rng).unwrap();
+            assert_eq!(simulated, Some(100));
+        }
+    }
+}
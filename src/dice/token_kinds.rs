@@ -1,31 +1,57 @@
-use std::{str::FromStr, fmt};
+use rand::RngCore;
+use std::{cmp::Reverse, num::{IntErrorKind, ParseIntError}, str::FromStr, fmt};
 use super::{
     dice_errors::RollError,
-    pool::Pool,
+    die::Die,
+    pool::{Pool, MAX_DICE},
     roll_token::RollToken,
     roll_value::RollValue,
     value_kinds::*,
     genesymbols::GeneSymbol,
+    l5rsymbols::L5rSymbol,
 };
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Argument {
-    Single(u8),
-    Array(Vec<u8>),
+    Single(u16),
+    Array(Vec<u16>),
+    Fudge,
+    Percentile,
+}
+
+impl Argument {
+    // Results of dice and operations can be used as arguments, but have to fit in the same range as numbers typed in directly
+    pub fn from_decimal(number: f64) -> Result<Self, RollError> {
+        if !number.is_finite() || number < 0.0 { return Err(RollError::ArgumentError); }
+        if number > u16::MAX as f64 { return Err(RollError::OverflowError); }
+
+        Ok(Argument::Single(number as u16))
+    }
+
+    // Numbers that are too big get the same error as results that are too big, rather than a parse error
+    fn parse_number(s: &str) -> Result<u16, RollError> {
+        s.parse().map_err(|error: ParseIntError| match error.kind() {
+            IntErrorKind::PosOverflow => RollError::OverflowError,
+            _ => RollError::ParseError(error),
+        })
+    }
 }
 
 impl FromStr for Argument {
     type Err = RollError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("f") { return Ok(Argument::Fudge); }
+        if s.trim().eq_ignore_ascii_case("p") { return Ok(Argument::Percentile); }
+
         if let Some(array_string) = s.trim().strip_prefix('[').unwrap_or("").strip_suffix(']') {
-            let mut args_array = Vec::<u8>::new();
+            let mut args_array = Vec::<u16>::new();
             for number_str in array_string.split_terminator(',') {
-                args_array.push(number_str.trim().parse()?);
+                args_array.push(Argument::parse_number(number_str.trim())?);
             }
             Ok(Argument::Array(args_array))
         } else {
-            Ok(Argument::Single(s.parse()?))
+            Ok(Argument::Single(Argument::parse_number(s)?))
         }
     }
 }
@@ -35,6 +61,8 @@ impl fmt::Display for Argument {
         match self {
             Argument::Array(array) => write!(f, "{:?}", array),
             Argument::Single(single) => write!(f, "{}", single),
+            Argument::Fudge => write!(f, "F"),
+            Argument::Percentile => write!(f, "%"),
         }
     }
 }
@@ -43,16 +71,34 @@ impl fmt::Display for Argument {
 pub struct Dice{pub pool: Option<Pool>}
 
 impl Dice {
-    pub fn apply(&self, left: Argument, right: Argument) -> Result<Self, RollError> {
+    pub fn apply(&self, left: Argument, right: Argument, rng: &mut dyn RngCore) -> Result<Self, RollError> {
+        let total_dice = match (&left, &right) {
+            (Argument::Single(number), Argument::Single(_)) => *number as usize,
+            (Argument::Single(number), Argument::Array(sides)) => *number as usize * sides.len(),
+            (Argument::Array(numbers), Argument::Single(_)) => numbers.iter().map(|&n| n as usize).sum(),
+            (Argument::Array(numbers), Argument::Array(sides)) => numbers.iter().zip(sides.iter()).map(|(&n, _)| n as usize).sum(),
+            (Argument::Single(number), Argument::Fudge) => *number as usize,
+            (Argument::Array(numbers), Argument::Fudge) => numbers.iter().map(|&n| n as usize).sum(),
+            (Argument::Single(number), Argument::Percentile) => *number as usize + 1,
+            (Argument::Array(numbers), Argument::Percentile) => numbers.iter().map(|&n| n as usize).sum::<usize>() + 1,
+            (Argument::Fudge | Argument::Percentile, _) => return Err(RollError::ArgumentError),
+        };
+        if total_dice > MAX_DICE { return Err(RollError::OverflowError); }
+
         let pool = match left {
             Argument::Single(number) => match right {
-                Argument::Single(sides) => Some(Pool::new(number, sides)),
-                Argument::Array(sides) => Some(Pool::new_dice_array(number, &sides)),
+                Argument::Single(sides) => Some(Pool::new(number, sides, rng)),
+                Argument::Array(sides) => Some(Pool::new_dice_array(number, &sides, rng)),
+                Argument::Fudge => Some(Pool::new_fudge(number, rng)),
+                Argument::Percentile => Some(Pool::new_percentile(number, rng)),
             },
             Argument::Array(number) => match right {
-                Argument::Single(sides) => Some(Pool::new_numbers_array(&number, sides)),
-                Argument::Array(sides) => Some(Pool::new_from_arrays(&number, &sides)),
+                Argument::Single(sides) => Some(Pool::new_numbers_array(&number, sides, rng)),
+                Argument::Array(sides) => Some(Pool::new_from_arrays(&number, &sides, rng)),
+                Argument::Fudge => Some(Pool::new_fudge(total_dice as u16, rng)),
+                Argument::Percentile => Some(Pool::new_percentile(total_dice as u16 - 1, rng)),
             },
+            Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
         };
 
         Ok(Dice{ pool })
@@ -63,7 +109,7 @@ impl Dice {
     }
 
     pub fn value(self) -> Result<RollValue, RollError> {
-        Ok(self.pool()?.total().into())
+        Ok(self.pool()?.value())
     }
 
     pub fn description(&self) -> String {
@@ -72,6 +118,8 @@ impl Dice {
         let (numbers, sides) = (pool.numbers(), pool.sides());
 
         match numbers.len() {
+            _ if pool.is_fudge() => format!("Rolled {}dF", pool.total_number()),
+            _ if pool.is_percentile() => format!("Rolled {}d% (tens dice, plus a units die)", pool.total_number() - 1),
             1 => match sides.len() {
                 1 => format!("Rolled {}d{}", numbers[0], sides[0]),
                 _ => format!("Rolled {}d{:?}", numbers[0], sides)
@@ -109,6 +157,8 @@ impl fmt::Display for Dice {
         let (numbers, sides) = (pool.numbers(), pool.sides());
 
         let dice_code = match numbers.len() {
+            _ if pool.is_fudge() => format!("{}dF", pool.total_number()),
+            _ if pool.is_percentile() => format!("{}d%", pool.total_number() - 1),
             1 => match sides.len() {
                 1 => format!("{}d{}", numbers[0], sides[0]),
                 _ => format!("{}d{:?}", numbers[0], sides)
@@ -188,6 +238,9 @@ pub struct Merge {
 
 impl Merge {
     pub fn apply(&self, left: RollToken, right: RollToken) -> Result<Self, RollError> {
+        if let (Ok(left_pool), Ok(right_pool)) = (left.clone().pool(), right.clone().pool()) {
+            if left_pool.dice().len() + right_pool.dice().len() > MAX_DICE { return Err(RollError::OverflowError); }
+        }
         Ok(Merge { left: Some(Box::new(left)), right: Some(Box::new(right)) })
     }
 
@@ -233,36 +286,48 @@ impl fmt::Display for Merge {
 #[derive(Clone, Debug, PartialEq)]
 pub enum Conversion {
     Genesys(GenesysDice),
+    L5r(L5rDice),
+    Crit(Crit),
 }
 
 impl Conversion {
-    pub fn apply(&self, token: RollToken) -> Result<Self, RollError> {
+    pub fn apply(&self, token: RollToken, rng: &mut dyn RngCore) -> Result<Self, RollError> {
         match self {
             Conversion::Genesys(g_dice) => Ok(Conversion::Genesys(g_dice.apply(token.pool()?)?)),
+            Conversion::L5r(l_dice) => Ok(Conversion::L5r(l_dice.apply(token.pool()?)?)),
+            Conversion::Crit(crit) => Ok(Conversion::Crit(crit.apply(token.pool()?, rng)?)),
         }
     }
 
     pub fn pool(self) -> Result<Pool, RollError> {
         match self {
-            Conversion::Genesys(g_dice) => g_dice.pool()
+            Conversion::Genesys(g_dice) => g_dice.pool(),
+            Conversion::L5r(l_dice) => l_dice.pool(),
+            Conversion::Crit(crit) => crit.pool(),
         }
     }
 
     pub fn value(&self) -> Result<RollValue, RollError> {
         match self {
             Conversion::Genesys(g_dice) => g_dice.value(),
+            Conversion::L5r(l_dice) => l_dice.value(),
+            Conversion::Crit(crit) => crit.value(),
         }
     }
 
     pub fn description(&self) -> String {
         match self {
             Conversion::Genesys(g_dice) => g_dice.description(),
+            Conversion::L5r(l_dice) => l_dice.description(),
+            Conversion::Crit(crit) => crit.description(),
         }
     }
 
     pub fn verbose(&self) -> String {
         match self {
             Conversion::Genesys(g_dice) => g_dice.verbose(),
+            Conversion::L5r(l_dice) => l_dice.verbose(),
+            Conversion::Crit(crit) => crit.verbose(),
         }
     }
 }
@@ -273,6 +338,10 @@ impl FromStr for Conversion {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(g_dice) = s.parse() {
             Ok(Conversion::Genesys(g_dice))
+        } else if let Ok(l_dice) = s.parse() {
+            Ok(Conversion::L5r(l_dice))
+        } else if let Ok(crit) = s.parse() {
+            Ok(Conversion::Crit(crit))
         } else {
             Err(RollError::SymbolError(s.into()))
         }
@@ -283,6 +352,8 @@ impl fmt::Display for Conversion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Conversion::Genesys(g_dice) => write!(f, "{}", g_dice),
+            Conversion::L5r(l_dice) => write!(f, "{}", l_dice),
+            Conversion::Crit(crit) => write!(f, "{}", crit),
         }
     }
 }
@@ -295,6 +366,7 @@ pub enum GenesysDice {
     Difficulty{base: Option<Pool>, res: Vec<Vec<GeneSymbol>>},
     Proficiency{base: Option<Pool>, res: Vec<Vec<GeneSymbol>>},
     Challenge{base: Option<Pool>, res: Vec<Vec<GeneSymbol>>},
+    Force{base: Option<Pool>, res: Vec<Vec<GeneSymbol>>},
 }
 
 impl GenesysDice {
@@ -338,6 +410,12 @@ impl GenesysDice {
                 }
                 Ok(GenesysDice::Challenge { base: Some(pool), res })
             },
+            GenesysDice::Force { base: _, res: _ } => {
+                for die in pool.dice() {
+                    res.push(GeneSymbol::force(*die));
+                }
+                Ok(GenesysDice::Force { base: Some(pool), res })
+            },
         }
     }
 
@@ -349,6 +427,7 @@ impl GenesysDice {
             GenesysDice::Difficulty { base, res: _ } => base.ok_or(RollError::NotResolvedError),
             GenesysDice::Proficiency { base, res: _ } => base.ok_or(RollError::NotResolvedError),
             GenesysDice::Challenge { base, res: _ } => base.ok_or(RollError::NotResolvedError),
+            GenesysDice::Force { base, res: _ } => base.ok_or(RollError::NotResolvedError),
         }
     }
 
@@ -360,6 +439,7 @@ impl GenesysDice {
             GenesysDice::Difficulty { base: _, res } => Ok(RollValue::Genesys(GenesysValue::new(res))),
             GenesysDice::Proficiency { base: _, res } => Ok(RollValue::Genesys(GenesysValue::new(res))),
             GenesysDice::Challenge { base: _, res } => Ok(RollValue::Genesys(GenesysValue::new(res))),
+            GenesysDice::Force { base: _, res } => Ok(RollValue::Genesys(GenesysValue::new(res))),
         }
     }
 
@@ -371,17 +451,19 @@ impl GenesysDice {
             GenesysDice::Difficulty { base: _, res: _ } => String::from("Convert numeric results to difficulty die values"),
             GenesysDice::Proficiency { base: _, res: _ } => String::from("Convert numeric results to proficiency die values"),
             GenesysDice::Challenge { base: _, res: _ } => String::from("Convert numeric results to challenge die values"),
+            GenesysDice::Force { base: _, res: _ } => String::from("Convert numeric results to force die values"),
         }
     }
 
     pub fn verbose(&self) -> String {
         match self {
-            GenesysDice::Boost { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
-            GenesysDice::Setback { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
-            GenesysDice::Ability { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
-            GenesysDice::Difficulty { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
-            GenesysDice::Proficiency { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
-            GenesysDice::Challenge { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::new(0, 0)), res),
+            GenesysDice::Boost { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Setback { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Ability { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Difficulty { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Proficiency { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Challenge { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            GenesysDice::Force { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
         }
     }
 }
@@ -398,6 +480,7 @@ impl FromStr for GenesysDice {
                 "d" => Ok(GenesysDice::Difficulty { base: None, res: vec![] }),
                 "p" => Ok(GenesysDice::Proficiency { base: None, res: vec![] }),
                 "c" => Ok(GenesysDice::Challenge { base: None, res: vec![] }),
+                "f" => Ok(GenesysDice::Force { base: None, res: vec![] }),
                 _ => Err(RollError::SymbolError(s.into())),
             }
         } else {
@@ -415,6 +498,131 @@ impl fmt::Display for GenesysDice {
             GenesysDice::Difficulty { base: _, res } => write!(f, "Difficulty: {:?}", res),
             GenesysDice::Proficiency { base: _, res } => write!(f, "Proficiency: {:?}", res),
             GenesysDice::Challenge { base: _, res } => write!(f, "Challenge: {:?}", res),
+            GenesysDice::Force { base: _, res } => write!(f, "Force: {:?}", res),
+        }
+    }
+}
+
+// Critical hit damage in D&D: roll all the dice in the pool a second time
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crit {
+    pub extra: Option<Pool>,
+    pub res: Option<Pool>,
+}
+
+impl Crit {
+    pub fn apply(&self, pool: Pool, rng: &mut dyn RngCore) -> Result<Self, RollError> {
+        let extra = pool.roll_again(rng)?;
+        let res = pool.add(&extra);
+
+        Ok(Crit { extra: Some(extra), res: Some(res) })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        Ok(self.res.as_ref().ok_or(RollError::NotResolvedError)?.value())
+    }
+
+    pub fn description(&self) -> String {
+        String::from("Double the dice for a critical hit")
+    }
+
+    pub fn verbose(&self) -> String {
+        format!(
+            "Roll {} extra di(c)e for a critical hit: {}, result: {}",
+            self.extra.as_ref().unwrap_or(&Pool::empty()).total_number(),
+            self.extra.as_ref().unwrap_or(&Pool::empty()),
+            self.res.as_ref().unwrap_or(&Pool::empty())
+        )
+    }
+}
+
+impl FromStr for Crit {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "crit" => Ok(Crit { extra: None, res: None }),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Crit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "crit -> {}", self.res.as_ref().unwrap_or(&Pool::empty()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum L5rDice {
+    Ring{base: Option<Pool>, res: Vec<Vec<L5rSymbol>>},
+    Skill{base: Option<Pool>, res: Vec<Vec<L5rSymbol>>},
+}
+
+impl L5rDice {
+    pub fn apply(&self, pool: Pool) -> Result<Self, RollError> {
+        match self {
+            L5rDice::Ring { base: _, res: _ } => {
+                let res = pool.dice().iter().map(|die| L5rSymbol::ring(*die)).collect();
+                Ok(L5rDice::Ring { base: Some(pool), res })
+            },
+            L5rDice::Skill { base: _, res: _ } => {
+                let res = pool.dice().iter().map(|die| L5rSymbol::skill(*die)).collect();
+                Ok(L5rDice::Skill { base: Some(pool), res })
+            },
+        }
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        match self {
+            L5rDice::Ring { base, res: _ } => base.ok_or(RollError::NotResolvedError),
+            L5rDice::Skill { base, res: _ } => base.ok_or(RollError::NotResolvedError),
+        }
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        match self {
+            L5rDice::Ring { base: _, res } => Ok(RollValue::L5r(L5rValue::new(res))),
+            L5rDice::Skill { base: _, res } => Ok(RollValue::L5r(L5rValue::new(res))),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            L5rDice::Ring { base: _, res: _ } => String::from("Convert numeric results to ring die values"),
+            L5rDice::Skill { base: _, res: _ } => String::from("Convert numeric results to skill die values"),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        match self {
+            L5rDice::Ring { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+            L5rDice::Skill { base, res } => format!("{} -> {:?}", base.as_ref().unwrap_or(&Pool::empty()), res),
+        }
+    }
+}
+
+impl FromStr for L5rDice {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "lr" => Ok(L5rDice::Ring { base: None, res: vec![] }),
+            "ls" => Ok(L5rDice::Skill { base: None, res: vec![] }),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for L5rDice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            L5rDice::Ring { base: _, res } => write!(f, "Ring: {:?}", res),
+            L5rDice::Skill { base: _, res } => write!(f, "Skill: {:?}", res),
         }
     }
 }
@@ -424,58 +632,100 @@ pub enum Operator {
     Cap(Cap),
     Explode(Explode),
     Keep(Keep),
+    Drop(Drop),
     Reroll(Reroll),
     Target(Target),
+    Shadowrun(Shadowrun),
+    Pbta(Pbta),
+    Cortex(Cortex),
+    Ironsworn(Ironsworn),
+    Ore(Ore),
+    Coc(Coc),
 }
 
 impl Operator {
-    pub fn apply(&self, token: RollToken, argument: Argument) -> Result<Self, RollError> {
+    pub fn apply(&self, token: RollToken, argument: Argument, rng: &mut dyn RngCore) -> Result<Self, RollError> {
         match self {
-            Operator::Cap(cap) => Ok(Operator::Cap(cap.apply(token, argument)?)),
-            Operator::Explode(explode) => Ok(Operator::Explode(explode.apply(token.pool()?, argument)?)),
+            Operator::Cap(cap) => Ok(Operator::Cap(cap.apply(token.pool()?, argument)?)),
+            Operator::Explode(explode) => Ok(Operator::Explode(explode.apply(token.pool()?, argument, rng)?)),
             Operator::Keep(keep) => Ok(Operator::Keep(keep.apply(token.pool()?, argument)?)),
-            Operator::Reroll(reroll) => Ok(Operator::Reroll(reroll.apply(token.pool()?, argument)?)),
+            Operator::Drop(drop) => Ok(Operator::Drop(drop.apply(token.pool()?, argument)?)),
+            Operator::Reroll(reroll) => Ok(Operator::Reroll(reroll.apply(token.pool()?, argument, rng)?)),
             Operator::Target(target) => Ok(Operator::Target(target.apply(token, argument)?)),
+            Operator::Shadowrun(shadowrun) => Ok(Operator::Shadowrun(shadowrun.apply(token.pool()?, argument)?)),
+            Operator::Pbta(pbta) => Ok(Operator::Pbta(pbta.apply(token, argument)?)),
+            Operator::Cortex(cortex) => Ok(Operator::Cortex(cortex.apply(token.pool()?, argument)?)),
+            Operator::Ironsworn(ironsworn) => Ok(Operator::Ironsworn(ironsworn.apply(token.pool()?, argument)?)),
+            Operator::Ore(ore) => Ok(Operator::Ore(ore.apply(token.pool()?, argument)?)),
+            Operator::Coc(coc) => Ok(Operator::Coc(coc.apply(token.pool()?, argument)?)),
         }
     }
 
     pub fn pool(self) -> Result<Pool, RollError> {
         match self {
-            Operator::Cap(_) => todo!(),
+            Operator::Cap(cap) => cap.pool(),
             Operator::Explode(explode) => explode.pool(),
             Operator::Keep(keep) => keep.pool(),
+            Operator::Drop(drop) => drop.pool(),
             Operator::Reroll(reroll) => reroll.pool(),
             Operator::Target(target) => target.pool(),
+            Operator::Shadowrun(shadowrun) => shadowrun.pool(),
+            Operator::Pbta(_) => Err(RollError::MissingPoolError),
+            Operator::Cortex(cortex) => cortex.pool(),
+            Operator::Ironsworn(ironsworn) => ironsworn.pool(),
+            Operator::Ore(ore) => ore.pool(),
+            Operator::Coc(coc) => coc.pool(),
         }
     }
 
     pub fn value(&self) -> Result<RollValue, RollError> {
         match self {
-            Operator::Cap(_) => todo!(),
+            Operator::Cap(cap) => cap.value(),
             Operator::Explode(explode) => explode.value(),
             Operator::Keep(keep) => keep.value(),
+            Operator::Drop(drop) => drop.value(),
             Operator::Reroll(reroll) => reroll.value(),
             Operator::Target(target) => Ok(target.value()),
+            Operator::Shadowrun(shadowrun) => Ok(shadowrun.value()),
+            Operator::Pbta(pbta) => pbta.value(),
+            Operator::Cortex(cortex) => Ok(cortex.value()),
+            Operator::Ironsworn(ironsworn) => ironsworn.value(),
+            Operator::Ore(ore) => ore.value(),
+            Operator::Coc(coc) => coc.value(),
         }
     }
 
     pub fn description(&self) -> String {
         match self {
-            Operator::Cap(_) => todo!(),
+            Operator::Cap(cap) => cap.description(),
             Operator::Explode(explode) => explode.description(),
             Operator::Keep(keep) => keep.description(),
+            Operator::Drop(drop) => drop.description(),
             Operator::Reroll(reroll) => reroll.description(),
             Operator::Target(target) => target.description(),
+            Operator::Shadowrun(shadowrun) => shadowrun.description(),
+            Operator::Pbta(pbta) => pbta.description(),
+            Operator::Cortex(cortex) => cortex.description(),
+            Operator::Ironsworn(ironsworn) => ironsworn.description(),
+            Operator::Ore(ore) => ore.description(),
+            Operator::Coc(coc) => coc.description(),
         }
     }
 
     pub fn verbose(&self) -> String {
         match self {
-            Operator::Cap(_) => todo!(),
+            Operator::Cap(cap) => cap.verbose(),
             Operator::Explode(explode) => explode.verbose(),
             Operator::Keep(keep) => keep.verbose(),
+            Operator::Drop(drop) => drop.verbose(),
             Operator::Reroll(reroll) => reroll.verbose(),
             Operator::Target(target) => target.verbose(),
+            Operator::Shadowrun(shadowrun) => shadowrun.verbose(),
+            Operator::Pbta(pbta) => pbta.verbose(),
+            Operator::Cortex(cortex) => cortex.verbose(),
+            Operator::Ironsworn(ironsworn) => ironsworn.verbose(),
+            Operator::Ore(ore) => ore.verbose(),
+            Operator::Coc(coc) => coc.verbose(),
         }
     }
 }
@@ -490,10 +740,24 @@ impl FromStr for Operator {
             Ok(Operator::Explode(explode))
         } else if let Ok(keep) = s.parse() {                // Attempt to parse into keep token
             Ok(Operator::Keep(keep))
+        } else if let Ok(drop) = s.parse() {
+            Ok(Operator::Drop(drop))
         } else if let Ok(reroll) = s.parse() {            // Attempt to parse into reroll token
             Ok(Operator::Reroll(reroll))
         } else if let Ok(target) = s.parse() {
             Ok(Operator::Target(target))
+        } else if let Ok(shadowrun) = s.parse() {
+            Ok(Operator::Shadowrun(shadowrun))
+        } else if let Ok(pbta) = s.parse() {
+            Ok(Operator::Pbta(pbta))
+        } else if let Ok(cortex) = s.parse() {
+            Ok(Operator::Cortex(cortex))
+        } else if let Ok(coc) = s.parse() {
+            Ok(Operator::Coc(coc))
+        } else if let Ok(ironsworn) = s.parse() {
+            Ok(Operator::Ironsworn(ironsworn))
+        } else if let Ok(ore) = s.parse() {
+            Ok(Operator::Ore(ore))
         } else {                                                  // If all these fail, error out
             Err(RollError::SymbolError(s.into()))
         }
@@ -503,24 +767,87 @@ impl FromStr for Operator {
 impl fmt::Display for Operator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Operator::Cap(_) => todo!(),
+            Operator::Cap(cap) => write!(f, "{}", cap),
             Operator::Explode(explode) => write!(f, "{}", explode),
             Operator::Keep(keep) => write!(f, "{}", keep),
+            Operator::Drop(drop) => write!(f, "{}", drop),
             Operator::Reroll(reroll) => write!(f, "{}", reroll),
             Operator::Target(target) => write!(f, "{}", target),
+            Operator::Shadowrun(shadowrun) => write!(f, "{}", shadowrun),
+            Operator::Pbta(pbta) => write!(f, "{}", pbta),
+            Operator::Cortex(cortex) => write!(f, "{}", cortex),
+            Operator::Ironsworn(ironsworn) => write!(f, "{}", ironsworn),
+            Operator::Ore(ore) => write!(f, "{}", ore),
+            Operator::Coc(coc) => write!(f, "{}", coc),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Cap {
-    Max{arg: Option<Argument>, res: Option<Pool>},
-    Min{arg: Option<Argument>, res: Option<Pool>},
+    Max{arg: Option<Argument>, res: Option<Pool>, capped: Option<Pool>},
+    Min{arg: Option<Argument>, res: Option<Pool>, capped: Option<Pool>},
 }
 
 impl Cap {
-    pub fn apply(&self, token: RollToken, argument: Argument) -> Result<Self, RollError> {
-        todo!()
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let arg = Some(argument.clone());
+        let mut capped_pool = pool;
+
+        match self {
+            Cap::Max { arg: _, res: _, capped: _ } => {
+                let capped = match argument {
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+                    Argument::Array(cap_array) if cap_array.len() == 1 => capped_pool.cap_max(cap_array[0]),
+                    Argument::Array(cap_array) => capped_pool.cap_max_specific(&cap_array)?,
+                    Argument::Single(cap) => capped_pool.cap_max(cap),
+                };
+                Ok(Cap::Max { arg, res: Some(capped_pool), capped: Some(capped) })
+            },
+            Cap::Min { arg: _, res: _, capped: _ } => {
+                let capped = match argument {
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+                    Argument::Array(cap_array) if cap_array.len() == 1 => capped_pool.cap_min(cap_array[0]),
+                    Argument::Array(cap_array) => capped_pool.cap_min_specific(&cap_array)?,
+                    Argument::Single(cap) => capped_pool.cap_min(cap),
+                };
+                Ok(Cap::Min { arg, res: Some(capped_pool), capped: Some(capped) })
+            },
+        }
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        match self {
+            Cap::Max { arg: _, res: pool, capped: _ } => pool.ok_or(RollError::NotResolvedError),
+            Cap::Min { arg: _, res: pool, capped: _ } => pool.ok_or(RollError::NotResolvedError),
+        }
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        match self {
+            Cap::Max { arg: _, res: pool, capped: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Cap::Min { arg: _, res: pool, capped: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Cap::Max { arg, res: _, capped: _ } => format!("Cap dice at a maximum of {}", arg.as_ref().unwrap_or(&Argument::Single(0))),
+            Cap::Min { arg, res: _, capped: _ } => format!("Cap dice at a minimum of {}", arg.as_ref().unwrap_or(&Argument::Single(0))),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        match self {
+            Cap::Max { arg: _, res, capped } | Cap::Min { arg: _, res, capped } => {
+                format!(
+                    "Cap {} di(c)e showing {}, result: {}",
+                    capped.as_ref().unwrap_or(&Pool::empty()).total_number(),
+                    capped.as_ref().unwrap_or(&Pool::empty()),
+                    res.as_ref().unwrap_or(&Pool::empty())
+                )
+            },
+        }
     }
 }
 
@@ -530,8 +857,8 @@ impl FromStr for Cap {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some(mode) = s.trim().strip_prefix('c') {
             match mode {
-                "" | "h" | "max"    => Ok(Cap::Max { arg: None, res: None }),
-                "l" | "min"         => Ok(Cap::Min { arg: None, res: None }),
+                "" | "h" | "max"    => Ok(Cap::Max { arg: None, res: None, capped: None }),
+                "l" | "min"         => Ok(Cap::Min { arg: None, res: None, capped: None }),
                 _           => Err(RollError::SymbolError(s.into()))
             }
         } else {
@@ -540,6 +867,15 @@ impl FromStr for Cap {
     }
 }
 
+impl fmt::Display for Cap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cap::Max { arg, res, capped: _ } => write!(f, "cap at maximum {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Cap::Min { arg, res, capped: _ } => write!(f, "cap at minimum {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Explode {
     Additive{arg: Option<Argument>, res: Vec<Pool>},
@@ -548,28 +884,31 @@ pub enum Explode {
 }
 
 impl Explode {
-    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+    pub fn apply(&self, pool: Pool, argument: Argument, rng: &mut dyn RngCore) -> Result<Self, RollError> {
         let arg = Some(argument.clone());
 
         match self {
             Explode::Additive { arg: _, res: _ } => {
                 let res = match argument {
-                    Argument::Single(explode_number) => pool.explode_n_additive(explode_number, true)?,
-                    Argument::Array(explode_array) => pool.explode_specific_additive(&explode_array, true)?,
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+                    Argument::Single(explode_number) => pool.explode_n_additive(explode_number, true, rng)?,
+                    Argument::Array(explode_array) => pool.explode_specific_additive(&explode_array, true, rng)?,
                 };
                 Ok(Explode::Additive { arg, res })
             },
             Explode::Once { arg: _, res: _ } => {
                 let res = match argument {
-                    Argument::Single(explode_number) => pool.explode_n(explode_number, false)?,
-                    Argument::Array(explode_array) => pool.explode_specific(&explode_array, false)?,
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+                    Argument::Single(explode_number) => pool.explode_n(explode_number, false, rng)?,
+                    Argument::Array(explode_array) => pool.explode_specific(&explode_array, false, rng)?,
                 };
                 Ok(Explode::Once { arg, res })
             },
             Explode::Recursive { arg: _, res: _ } => {
                 let res = match argument {
-                    Argument::Single(explode_number) => pool.explode_n(explode_number, true)?,
-                    Argument::Array(explode_array) => pool.explode_specific(&explode_array, true)?,
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+                    Argument::Single(explode_number) => pool.explode_n(explode_number, true, rng)?,
+                    Argument::Array(explode_array) => pool.explode_specific(&explode_array, true, rng)?,
                 };
                 Ok(Explode::Recursive { arg, res })
             },
@@ -581,7 +920,7 @@ impl Explode {
             Explode::Additive { arg: _, res } => {
                 match res.len() {
                     0 => Err(RollError::MissingPoolError),
-                    _ => Ok(res.last().unwrap_or(&Pool::new(0, 0)).clone()),
+                    _ => Ok(res.last().unwrap_or(&Pool::empty()).clone()),
                 }
             },
             Explode::Once { arg: _, res } => {
@@ -595,14 +934,14 @@ impl Explode {
             Explode::Recursive { arg: _, res } => {
                 match res.len() {
                     0 => Err(RollError::MissingPoolError),
-                    _ => Ok(res.iter().fold(Pool::new(0, 0), |final_pool, pool| final_pool.add(pool)))
+                    _ => Ok(res.iter().fold(Pool::empty(), |final_pool, pool| final_pool.add(pool)))
                 }
             },
         }
     }
 
     pub fn value(&self) -> Result<RollValue, RollError> {
-        Ok(self.clone().pool()?.total().into())
+        Ok(self.clone().pool()?.value())
     }
 
     pub fn description(&self) -> String {
@@ -649,7 +988,7 @@ impl Explode {
                 }
                 summary = match summary.len() {
                     0 => format!("No exploded dice -> {}", res[0]),
-                    _ => format!("{}Total: {}", summary, res.iter().fold(Pool::new(0, 0), |final_pool, pool| final_pool.add(pool)))
+                    _ => format!("{}Total: {}", summary, res.iter().fold(Pool::empty(), |final_pool, pool| final_pool.add(pool)))
                 };
                 summary
             },
@@ -677,9 +1016,9 @@ impl FromStr for Explode {
 impl fmt::Display for Explode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Explode::Additive { arg, res: _ } => write!(f, "explode additive {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::new(0, 0))),
-            Explode::Once { arg, res: _ } => write!(f, "explode once {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::new(0, 0))),
-            Explode::Recursive { arg, res: _ } => write!(f, "explode recursive {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::new(0, 0))),
+            Explode::Additive { arg, res: _ } => write!(f, "explode additive {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::empty())),
+            Explode::Once { arg, res: _ } => write!(f, "explode once {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::empty())),
+            Explode::Recursive { arg, res: _ } => write!(f, "explode recursive {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), self.clone().pool().unwrap_or(Pool::empty())),
         }
     }
 }
@@ -698,6 +1037,7 @@ impl Keep {
         match self {
             Keep::Exact { arg: _, res: _ } => {
                 let res = match argument {
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
                     Argument::Array(keep_array) => Some(pool.keep_exact(&keep_array)),
                     Argument::Single(keep_number) => Some(pool.keep_exact(&[keep_number]))
                 };
@@ -705,6 +1045,7 @@ impl Keep {
             },
             Keep::High { arg: _, res: _ } => {
                 let res = match argument {
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
                     Argument::Array(keep_array) if keep_array.len() == 1 => Some(pool.keep_highest(keep_array[0])),
                     Argument::Array(_) => return Err(RollError::ArgumentError),
                     Argument::Single(keep_amount) => Some(pool.keep_highest(keep_amount))
@@ -713,6 +1054,7 @@ impl Keep {
             },
             Keep::Low { arg: _, res: _ } => {
                 let res = match argument {
+                    Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
                     Argument::Array(keep_array) if keep_array.len() == 1 => Some(pool.keep_lowest(keep_array[0])),
                     Argument::Array(_) => return Err(RollError::ArgumentError),
                     Argument::Single(keep_amount) => Some(pool.keep_lowest(keep_amount))
@@ -732,9 +1074,9 @@ impl Keep {
 
     pub fn value(&self) -> Result<RollValue, RollError> {
         match self {
-            Keep::Exact { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
-            Keep::High { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
-            Keep::Low { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
+            Keep::Exact { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Keep::High { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Keep::Low { arg: _, res: pool } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
         }
     }
 
@@ -749,17 +1091,17 @@ impl Keep {
     pub fn verbose(&self) -> String {
         match self {
             Keep::Exact { arg: _, res } => {
-                let default = Pool::new(0, 0);
+                let default = Pool::empty();
                 let result = res.as_ref().unwrap_or(&default);
                 format!("Keep {} dice -> {}", result.dice().len(), result)
             },
             Keep::Low { arg: _, res } => {
-                let default = Pool::new(0, 0);
+                let default = Pool::empty();
                 let result = res.as_ref().unwrap_or(&default);
                 format!("Keep {} lowest -> {}", result.dice().len(), result)
             },
             Keep::High { arg: _, res } => {
-                let default = Pool::new(0, 0);
+                let default = Pool::empty();
                 let result = res.as_ref().unwrap_or(&default);
                 format!("Keep {} highest -> {}", result.dice().len(), result)
             },
@@ -787,9 +1129,93 @@ impl FromStr for Keep {
 impl fmt::Display for Keep {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Keep::Exact { arg, res } => write!(f, "keep exactly {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
-            Keep::High { arg, res } => write!(f, "keep highest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
-            Keep::Low { arg, res } => write!(f, "keep lowest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
+            Keep::Exact { arg, res } => write!(f, "keep exactly {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Keep::High { arg, res } => write!(f, "keep highest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Keep::Low { arg, res } => write!(f, "keep lowest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+        }
+    }
+}
+
+// Dropping is the other side of keeping, and holds on to the dropped dice so they can still be shown
+#[derive(Clone, Debug, PartialEq)]
+pub enum Drop {
+    High{arg: Option<Argument>, res: Option<Pool>, dropped: Option<Pool>},
+    Low{arg: Option<Argument>, res: Option<Pool>, dropped: Option<Pool>},
+}
+
+impl Drop {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let arg = Some(argument.clone());
+        let drop_amount = match argument {
+            Argument::Fudge | Argument::Percentile => return Err(RollError::ArgumentError),
+            Argument::Array(drop_array) if drop_array.len() == 1 => drop_array[0],
+            Argument::Array(_) => return Err(RollError::ArgumentError),
+            Argument::Single(drop_amount) => drop_amount,
+        };
+
+        match self {
+            Drop::High { .. } => {
+                let (res, dropped) = pool.drop_dice(drop_amount, true);
+                Ok(Drop::High { arg, res: Some(res), dropped: Some(dropped) })
+            },
+            Drop::Low { .. } => {
+                let (res, dropped) = pool.drop_dice(drop_amount, false);
+                Ok(Drop::Low { arg, res: Some(res), dropped: Some(dropped) })
+            },
+        }
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        match self {
+            Drop::High { res, .. } => res.ok_or(RollError::NotResolvedError),
+            Drop::Low { res, .. } => res.ok_or(RollError::NotResolvedError),
+        }
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        match self {
+            Drop::High { res, .. } => Ok(res.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Drop::Low { res, .. } => Ok(res.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Drop::High { arg, .. } => format!("Drop highest {} di(c)e", arg.as_ref().unwrap_or(&Argument::Single(0))),
+            Drop::Low { arg, .. } => format!("Drop lowest {} di(c)e", arg.as_ref().unwrap_or(&Argument::Single(0))),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        let (which, res, dropped) = match self {
+            Drop::High { res, dropped, .. } => ("highest", res, dropped),
+            Drop::Low { res, dropped, .. } => ("lowest", res, dropped),
+        };
+        let default = Pool::empty();
+        let dropped = dropped.as_ref().unwrap_or(&default);
+        let struck: Vec<String> = dropped.dice().iter().map(|die| format!("~~{}~~", die)).collect();
+
+        format!("Drop {} {} -> [{}], result: {}", dropped.dice().len(), which, struck.join(", "), res.as_ref().unwrap_or(&default))
+    }
+}
+
+impl FromStr for Drop {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "dh" => Ok(Drop::High { arg: None, res: None, dropped: None }),
+            "dl" => Ok(Drop::Low { arg: None, res: None, dropped: None }),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Drop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Drop::High { arg, res, .. } => write!(f, "drop highest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Drop::Low { arg, res, .. } => write!(f, "drop lowest {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
         }
     }
 }
@@ -803,55 +1229,59 @@ pub enum Reroll {
 }
 
 impl Reroll {
-    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+    pub fn apply(&self, pool: Pool, argument: Argument, rng: &mut dyn RngCore) -> Result<Self, RollError> {
         let arg = Some(argument.clone());
         let mut rerolled_pool = pool.clone();
 
         match self {
             Reroll::Better { arg: _, res: _, rerolls: _ } => {
                 match argument {
+                    Argument::Fudge | Argument::Percentile => Err(RollError::ArgumentError),
                     Argument::Array(array) => {
-                        let new_dice = rerolled_pool.reroll_specific_better(&array);
+                        let new_dice = rerolled_pool.reroll_specific_better(&array, rng);
                         Ok(Reroll::Better { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     },
                     Argument::Single(reroll_number) => {
-                        let new_dice = rerolled_pool.reroll_n_better(reroll_number);
+                        let new_dice = rerolled_pool.reroll_n_better(reroll_number, rng);
                         Ok(Reroll::Better { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     }
                 }
             },
             Reroll::Once { arg: _, res: _, rerolls: _ } => {
                 match argument {
+                    Argument::Fudge | Argument::Percentile => Err(RollError::ArgumentError),
                     Argument::Array(array) => {
-                        let new_dice = rerolled_pool.reroll_specific(&array);
+                        let new_dice = rerolled_pool.reroll_specific(&array, rng);
                         Ok(Reroll::Once { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     },
                     Argument::Single(reroll_number) => {
-                        let new_dice = rerolled_pool.reroll_n(reroll_number);
+                        let new_dice = rerolled_pool.reroll_n(reroll_number, rng);
                         Ok(Reroll::Once { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     }
                 }
             },
             Reroll::Recursive { arg: _, res: _, rerolls: _ } => {
                 match argument {
+                    Argument::Fudge | Argument::Percentile => Err(RollError::ArgumentError),
                     Argument::Array(array) => {
-                        let new_dice = rerolled_pool.reroll_specific_recursive(&array);
+                        let new_dice = rerolled_pool.reroll_specific_recursive(&array, rng);
                         Ok(Reroll::Recursive { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     },
                     Argument::Single(reroll_number) => {
-                        let new_dice = rerolled_pool.reroll_n_recursive(reroll_number);
+                        let new_dice = rerolled_pool.reroll_n_recursive(reroll_number, rng);
                         Ok(Reroll::Recursive { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     },
                 }
             },
             Reroll::Worse { arg: _, res: _, rerolls: _ } => {
                 match argument {
+                    Argument::Fudge | Argument::Percentile => Err(RollError::ArgumentError),
                     Argument::Array(array) => {
-                        let new_dice = rerolled_pool.reroll_specific_worse(&array);
+                        let new_dice = rerolled_pool.reroll_specific_worse(&array, rng);
                         Ok(Reroll::Worse { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     },
                     Argument::Single(reroll_number) => {
-                        let new_dice = rerolled_pool.reroll_n_worse(reroll_number);
+                        let new_dice = rerolled_pool.reroll_n_worse(reroll_number, rng);
                         Ok(Reroll::Worse { arg, res: Some(rerolled_pool), rerolls: Some(new_dice) })
                     }
                 }
@@ -870,10 +1300,10 @@ impl Reroll {
 
     pub fn value(&self) -> Result<RollValue, RollError> {
         match self {
-            Reroll::Better { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
-            Reroll::Once { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
-            Reroll::Recursive { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
-            Reroll::Worse { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.total().into()),
+            Reroll::Better { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Reroll::Once { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Reroll::Recursive { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
+            Reroll::Worse { arg: _, res: pool, rerolls: _ } => Ok(pool.as_ref().ok_or(RollError::NotResolvedError)?.value()),
         }
     }
 
@@ -891,33 +1321,33 @@ impl Reroll {
             Reroll::Better { arg: _, res, rerolls } => {
                 format!(
                     "Reroll {} di(c)e -> {}, result: {}", 
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)).total_number(),
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)),
-                    res.as_ref().unwrap_or(&Pool::new(0, 0))
+                    rerolls.as_ref().unwrap_or(&Pool::empty()).total_number(),
+                    rerolls.as_ref().unwrap_or(&Pool::empty()),
+                    res.as_ref().unwrap_or(&Pool::empty())
                 )
             },
             Reroll::Once { arg: _, res, rerolls } => {
                 format!(
                     "Reroll {} di(c)e -> {}, result: {}", 
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)).total_number(),
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)),
-                    res.as_ref().unwrap_or(&Pool::new(0, 0))
+                    rerolls.as_ref().unwrap_or(&Pool::empty()).total_number(),
+                    rerolls.as_ref().unwrap_or(&Pool::empty()),
+                    res.as_ref().unwrap_or(&Pool::empty())
                 )
             },
             Reroll::Recursive { arg: _, res, rerolls } => {
                 format!(
                     "Reroll {} di(c)e -> {}, result: {}", 
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)).total_number(),
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)),
-                    res.as_ref().unwrap_or(&Pool::new(0, 0))
+                    rerolls.as_ref().unwrap_or(&Pool::empty()).total_number(),
+                    rerolls.as_ref().unwrap_or(&Pool::empty()),
+                    res.as_ref().unwrap_or(&Pool::empty())
                 )
             },
             Reroll::Worse { arg: _, res, rerolls } => {
                 format!(
                     "Reroll {} di(c)e -> {}, result: {}", 
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)).total_number(),
-                    rerolls.as_ref().unwrap_or(&Pool::new(0, 0)),
-                    res.as_ref().unwrap_or(&Pool::new(0, 0))
+                    rerolls.as_ref().unwrap_or(&Pool::empty()).total_number(),
+                    rerolls.as_ref().unwrap_or(&Pool::empty()),
+                    res.as_ref().unwrap_or(&Pool::empty())
                 )
             },
         }
@@ -945,18 +1375,19 @@ impl FromStr for Reroll {
 impl fmt::Display for Reroll {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Reroll::Better { arg, res, rerolls: _ } => write!(f, "reroll keep better {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
-            Reroll::Once { arg, res, rerolls: _ } => write!(f, "reroll once {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
-            Reroll::Recursive { arg, res, rerolls: _ } => write!(f, "reroll recursively {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
-            Reroll::Worse { arg, res, rerolls: _ } => write!(f, "reroll keep worse {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::new(0, 0))),
+            Reroll::Better { arg, res, rerolls: _ } => write!(f, "reroll keep better {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Reroll::Once { arg, res, rerolls: _ } => write!(f, "reroll once {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Reroll::Recursive { arg, res, rerolls: _ } => write!(f, "reroll recursively {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
+            Reroll::Worse { arg, res, rerolls: _ } => write!(f, "reroll keep worse {} -> {}", arg.as_ref().unwrap_or(&Argument::Single(0)), res.as_ref().unwrap_or(&Pool::empty())),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Target {
-    Success{arg: Option<Argument>, pool: Option<Pool>, sux: i16},
-    Botch{arg: Option<Argument>, pool: Option<Pool>, sux: i16},
+    Success{arg: Option<Argument>, pool: Option<Pool>, sux: i32},
+    Botch{arg: Option<Argument>, pool: Option<Pool>, sux: i32, botch: bool},
+    Wfrp{arg: Option<Argument>, pool: Option<Pool>, roll: u16, sl: i32, passed: bool},
 }
 
 impl Target {
@@ -964,19 +1395,39 @@ impl Target {
         let arg = Some(argument.clone());
         let pool = Some(token.clone().pool()?);
         let base_sux = match &token {
-            RollToken::Operator(Operator::Target(target)) => target.value().to_decimal()? as i16,
+            RollToken::Operator(Operator::Target(target)) => target.value().to_decimal()? as i32,
             _ => 0,
         };
         match argument {
+            Argument::Fudge | Argument::Percentile => Err(RollError::ArgumentError),
             Argument::Single(threshold) => {
                 match self {
                     Target::Success { arg: _, pool: _, sux: _ } => {
-                        let sux = base_sux + token.pool()?.count_dice_over(threshold) as i16;
+                        let sux = base_sux + token.pool()?.count_dice_over(threshold) as i32;
                         Ok(Target::Success { arg, pool, sux })
                     },
-                    Target::Botch { arg: _, pool: _, sux: _ } => {
-                        let sux = base_sux - (token.pool()?.count_dice_under(threshold) as i16);
-                        Ok(Target::Botch { arg, pool, sux })
+                    Target::Botch { .. } => {
+                        let ones = token.pool()?.count_dice_under(threshold) as i32;
+                        // It's only a botch if there were no successes to cancel in the first place
+                        let botch = base_sux <= 0 && ones > 0;
+                        let sux = base_sux - ones;
+                        Ok(Target::Botch { arg, pool, sux, botch })
+                    },
+                    Target::Wfrp { .. } => {
+                        // Percentile dice read as a single d100 roll here, and a plain d100 works just as well
+                        let roll = token.pool()?.value().to_decimal()? as u16;
+                        let passed = match roll {
+                            1..=5 => true,
+                            96..=100 => false,
+                            _ => roll <= threshold,
+                        };
+                        // Success Levels are the difference in tens, and automatic successes and failures can't go past zero
+                        let sl = (threshold / 10) as i32 - (roll / 10) as i32;
+                        let sl = match passed {
+                            true => sl.max(0),
+                            false => sl.min(0),
+                        };
+                        Ok(Target::Wfrp { arg, pool, roll, sl, passed })
                     },
                 }
             },
@@ -991,11 +1442,11 @@ impl Target {
                             tns.copy_from_slice(&threshold_array[..max_sides]);
                         }
 
-                        let sux = token.pool()?.count_successes(&tns) as i16;
+                        let sux = token.pool()?.count_successes(&tns) as i32;
                         let arg = Some(Argument::Array(tns));
                         Ok(Target::Success { arg, pool, sux })
                     },
-                    Target::Botch { arg: _, pool: _, sux: _ } => {
+                    Target::Botch { .. } => {
                         let max_sides = token.clone().pool()?.sides_max() as usize;
                         let mut tns = vec![0; max_sides];
                         if tns.len() >= threshold_array.len() {
@@ -1004,10 +1455,12 @@ impl Target {
                             tns.copy_from_slice(&threshold_array[..max_sides]);
                         }
 
-                        let sux = - (token.pool()?.count_successes(&threshold_array) as i16);
+                        let sux = - (token.pool()?.count_successes(&threshold_array) as i32);
+                        let botch = base_sux <= 0 && sux < 0;
                         let arg = Some(Argument::Array(tns));
-                        Ok(Target::Botch { arg, pool, sux })
+                        Ok(Target::Botch { arg, pool, sux, botch })
                     },
+                    Target::Wfrp { .. } => Err(RollError::ArgumentError),
                 }
             },
         }
@@ -1016,14 +1469,16 @@ impl Target {
     pub fn pool(self) -> Result<Pool, RollError> {
         match self {
             Target::Success { arg: _, pool, sux: _ } => pool.ok_or(RollError::MissingPoolError),
-            Target::Botch { arg: _, pool, sux: _ } => pool.ok_or(RollError::MissingPoolError),
+            Target::Botch { pool, .. } => pool.ok_or(RollError::MissingPoolError),
+            Target::Wfrp { pool, .. } => pool.ok_or(RollError::MissingPoolError),
         }
     }
 
     pub fn value(&self) -> RollValue {
         match self {
             Target::Success { arg: _, pool: _, sux } => RollValue::Successes(*sux),
-            Target::Botch { arg: _, pool: _, sux } => RollValue::Successes(*sux),
+            Target::Botch { sux, .. } => RollValue::Successes(*sux),
+            Target::Wfrp { sl, .. } => RollValue::Successes(*sl),
         }
     }
 
@@ -1031,18 +1486,21 @@ impl Target {
         match self {
             Target::Success { arg, pool: _, sux: _ } => match arg {
                 Some(argument) => match argument {
+                    Argument::Fudge | Argument::Percentile => "Something went wrong! Pleasse let the boss know!".into(),
                     Argument::Single(n) => format!("Count one success per die showing {} or higher", n),
                     Argument::Array(a) => format!("Count successes: {:?}", a),
                 },
                 None => "Something went wrong! Pleasse let the boss know!".into(),
             },
-            Target::Botch { arg, pool: _, sux: _ } => match arg {
+            Target::Botch { arg, .. } => match arg {
                 Some(argument) => match argument {
+                    Argument::Fudge | Argument::Percentile => "Something went wrong! Pleasse let the boss know!".into(),
                     Argument::Single(n) => format!("Subtract one success per die showing {} or lower", n),
                     Argument::Array(a) => format!("Subtract successes: {:?}", a),
                 },
                 None => "Something went wrong! Pleasse let the boss know!".into(),
             },
+            Target::Wfrp { arg, .. } => format!("Roll {} or lower and count Success Levels", arg.as_ref().unwrap_or(&Argument::Single(0))),
         }
     }
 
@@ -1050,38 +1508,74 @@ impl Target {
         match self {
             Target::Success { arg, pool, sux } => match arg {
                 Some(argument) => match argument {
+                    Argument::Fudge | Argument::Percentile => "Something went wrong! Pleasse let the boss know!".into(),
                     Argument::Single(n) => format!(
                         "{} -> {:?} = {} success(es)",
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)),
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)).dice().iter().map(|d| if d.equal_or_greater(*n) {1} else {0}).collect::<Vec<i16>>(),
+                        pool.as_ref().unwrap_or(&Pool::empty()),
+                        pool.as_ref().unwrap_or(&Pool::empty()).dice().iter().map(|d| if d.equal_or_greater(*n) {1} else {0}).collect::<Vec<i32>>(),
                         sux
                     ),
                     Argument::Array(a) => format!(
                         "{} -> {:?} = {} success(es)",
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)),
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)).dice().iter().map(|d| d.count_successes(a)).collect::<Vec<u8>>(),
+                        pool.as_ref().unwrap_or(&Pool::empty()),
+                        pool.as_ref().unwrap_or(&Pool::empty()).dice().iter().map(|d| d.count_successes(a)).collect::<Vec<u16>>(),
                         sux
                     ),
                 },
                 None => "Something went wrong! Pleasse let the boss know!".into(),
             },
-            Target::Botch { arg, pool, sux } => match arg {
+            Target::Botch { arg, pool, sux, botch } => match arg {
                 Some(argument) => match argument {
+                    Argument::Fudge | Argument::Percentile => "Something went wrong! Pleasse let the boss know!".into(),
                     Argument::Single(n) => format!(
-                        "{} -> {:?} = {} success(es)",
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)),
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)).dice().iter().map(|d| if d.equal_or_less(*n) {-1} else {0}).collect::<Vec<i16>>(),
-                        sux
+                        "{} -> {:?} = {} success(es){}",
+                        pool.as_ref().unwrap_or(&Pool::empty()),
+                        pool.as_ref().unwrap_or(&Pool::empty()).dice().iter().map(|d| if d.equal_or_less(*n) {-1} else {0}).collect::<Vec<i32>>(),
+                        sux,
+                        if *botch { ", botch!" } else { "" }
                     ),
                     Argument::Array(a) => format!(
-                        "{} -> {:?} = {} success(es)",
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)),
-                        pool.as_ref().unwrap_or(&Pool::new(0, 0)).dice().iter().map(|d| - (d.count_successes(a) as i16)).collect::<Vec<i16>>(),
-                        sux
+                        "{} -> {:?} = {} success(es){}",
+                        pool.as_ref().unwrap_or(&Pool::empty()),
+                        pool.as_ref().unwrap_or(&Pool::empty()).dice().iter().map(|d| - (d.count_successes(a) as i32)).collect::<Vec<i32>>(),
+                        sux,
+                        if *botch { ", botch!" } else { "" }
                     ),
                 },
                 None => "Something went wrong! Pleasse let the boss know!".into(),
             },
+            Target::Wfrp { arg, pool, roll, passed, .. } => format!(
+                "{} -> rolled {} against {}: {}, {}",
+                pool.as_ref().unwrap_or(&Pool::empty()),
+                roll,
+                arg.as_ref().unwrap_or(&Argument::Single(0)),
+                if *passed { "success" } else { "failure" },
+                self.wfrp_summary(),
+            ),
+        }
+    }
+
+    // Rolling doubles (including 100, read as 00) makes a success critical and a failure a fumble
+    fn wfrp_summary(&self) -> String {
+        match self {
+            Target::Wfrp { roll, sl, passed, .. } => {
+                let sign = if *passed { "+" } else { "-" };
+                let double = match (roll % 100 / 10 == roll % 10, passed) {
+                    (false, _) => "",
+                    (true, true) => ", critical!",
+                    (true, false) => ", fumble!",
+                };
+                format!("SL {}{}{}", sign, sl.abs(), double)
+            },
+            _ => String::new(),
+        }
+    }
+
+    // Characteristic, roll, Success Levels and whether it passed, for reading WFRP tests back out of a roll
+    pub fn wfrp_result(&self) -> Option<(u16, u16, i32, bool)> {
+        match self {
+            Target::Wfrp { arg: Some(Argument::Single(characteristic)), roll, sl, passed, .. } => Some((*characteristic, *roll, *sl, *passed)),
+            _ => None,
         }
     }
 }
@@ -1092,7 +1586,8 @@ impl FromStr for Target {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "t" => Ok(Target::Success { arg: None, pool: None, sux: 0 }),
-            "b" => Ok(Target::Botch { arg: None, pool: None, sux: 0 }),
+            "b" => Ok(Target::Botch { arg: None, pool: None, sux: 0, botch: false }),
+            "wf" => Ok(Target::Wfrp { arg: None, pool: None, roll: 0, sl: 0, passed: false }),
             _   => Err(RollError::SymbolError(s.into()))
         }
     }
@@ -1103,6 +1598,7 @@ impl fmt::Display for Target {
         match self {
             Target::Success { arg, pool: _, sux } => {
                 match arg.as_ref().unwrap_or(&Argument::Single(0)) {
+                    Argument::Fudge | Argument::Percentile => write!(f, "no success counting rule given -> {} success(es)", sux),
                     Argument::Single(threshold) => {
                         write!(f, "success on {} or higher -> {} success(es)", threshold, sux)
                     },
@@ -1122,10 +1618,12 @@ impl fmt::Display for Target {
                     },
                 }
             },
-            Target::Botch { arg, pool: _, sux } => {
+            Target::Botch { arg, sux, botch, .. } => {
+                let botch = if *botch { ", botch!" } else { "" };
                 match arg.as_ref().unwrap_or(&Argument::Single(0)) {
+                    Argument::Fudge | Argument::Percentile => write!(f, "no success counting rule given -> {} success(es){}", sux, botch),
                     Argument::Single(threshold) => {
-                        write!(f, "subtract success on {} or lower -> {} success(es)", threshold, sux)
+                        write!(f, "subtract success on {} or lower -> {} success(es){}", threshold, sux, botch)
                     },
                     Argument::Array(thresh_array) => {
                         let t_values = thresh_array.iter().enumerate();
@@ -1137,12 +1635,635 @@ impl fmt::Display for Target {
                             }
                         });
                         match t_string.strip_suffix(", ") {
-                            Some(output) => write!(f, "subtract successes: {} -> {} success(es)", output, sux),
-                            None => write!(f, "no success counting rule given -> {} success(es)", sux),
+                            Some(output) => write!(f, "subtract successes: {} -> {} success(es){}", output, sux, botch),
+                            None => write!(f, "no success counting rule given -> {} success(es){}", sux, botch),
                         }
                     },
                 }
             },
+            Target::Wfrp { arg, roll, .. } => write!(f, "{} vs {} -> {}", roll, arg.as_ref().unwrap_or(&Argument::Single(0)), self.wfrp_summary()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Glitch {
+    Clean,
+    Standard,
+    Critical,
+}
+
+// Shadowrun hit counting: hits on 5+, capped by the limit (0 for no limit), and glitches when more than half the dice show 1s
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shadowrun {
+    pub arg: Option<Argument>,
+    pub res: Option<Pool>,
+    pub hits: i32,
+    pub glitch: Glitch,
+}
+
+impl Shadowrun {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let limit = match argument {
+            Argument::Single(limit) => limit,
+            _ => return Err(RollError::ArgumentError),
+        };
+
+        let raw_hits = pool.count_dice_over(5);
+        let hits = match limit {
+            0 => raw_hits,
+            _ => raw_hits.min(limit),
+        } as i32;
+
+        let ones = pool.count_dice_under(1) as usize;
+        let glitch = if ones * 2 <= pool.dice().len() {
+            Glitch::Clean
+        } else if raw_hits == 0 {
+            Glitch::Critical
+        } else {
+            Glitch::Standard
+        };
+
+        Ok(Shadowrun { arg: Some(argument), res: Some(pool), hits, glitch })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> RollValue {
+        RollValue::Successes(self.hits)
+    }
+
+    pub fn description(&self) -> String {
+        match self.arg {
+            Some(Argument::Single(limit)) if limit > 0 => format!("Count hits on 5 or higher, up to a limit of {}, and check for glitches", limit),
+            _ => String::from("Count hits on 5 or higher and check for glitches"),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        let pool = self.res.as_ref().unwrap_or(&Pool::empty()).clone();
+        format!(
+            "{} -> {} hit(s), {} one(s) out of {} di(c)e{}",
+            pool,
+            pool.count_dice_over(5),
+            pool.count_dice_under(1),
+            pool.total_number(),
+            self.glitch_string()
+        )
+    }
+
+    fn glitch_string(&self) -> &str {
+        match self.glitch {
+            Glitch::Clean => "",
+            Glitch::Standard => ", glitch!",
+            Glitch::Critical => ", critical glitch!",
+        }
+    }
+}
+
+impl FromStr for Shadowrun {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "sr" => Ok(Shadowrun { arg: None, res: None, hits: 0, glitch: Glitch::Clean }),
+            _ => Err(RollError::SymbolError(s.into()))
+        }
+    }
+}
+
+impl fmt::Display for Shadowrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.arg {
+            Some(Argument::Single(limit)) if limit > 0 => write!(f, "hits with limit {} -> {} hit(s){}", limit, self.hits, self.glitch_string()),
+            _ => write!(f, "hits -> {} hit(s){}", self.hits, self.glitch_string()),
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PbtaTier {
+    Miss,
+    WeakHit,
+    StrongHit,
+}
+
+impl fmt::Display for PbtaTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PbtaTier::Miss => write!(f, "Miss"),
+            PbtaTier::WeakHit => write!(f, "Weak hit"),
+            PbtaTier::StrongHit => write!(f, "Strong hit"),
+        }
+    }
+}
+
+// Powered by the Apocalypse outcome tiers. Takes the weak and strong hit thresholds, and leaves the total itself alone
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pbta {
+    pub arg: Option<Argument>,
+    pub total: f64,
+    pub tier: Option<PbtaTier>,
+}
+
+impl Pbta {
+    pub fn apply(&self, token: RollToken, argument: Argument) -> Result<Self, RollError> {
+        let (weak, strong) = match &argument {
+            Argument::Array(thresholds) if thresholds.len() == 2 && thresholds[0] <= thresholds[1] => (thresholds[0] as f64, thresholds[1] as f64),
+            _ => return Err(RollError::ArgumentError),
+        };
+
+        let total = token.value()?.to_decimal()?;
+        let tier = if total >= strong {
+            PbtaTier::StrongHit
+        } else if total >= weak {
+            PbtaTier::WeakHit
+        } else {
+            PbtaTier::Miss
+        };
+
+        Ok(Pbta { arg: Some(argument), total, tier: Some(tier) })
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        match self.tier {
+            Some(_) => Ok(RollValue::Decimal(self.total)),
+            None => Err(RollError::NotResolvedError),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match &self.arg {
+            Some(Argument::Array(thresholds)) if thresholds.len() == 2 => format!(
+                "Miss below {}, weak hit from {}, strong hit from {}",
+                thresholds[0], thresholds[0], thresholds[1]
+            ),
+            _ => "Something went wrong! Pleasse let the boss know!".into(),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        match self.tier {
+            Some(tier) => format!("{} -> {}", self.total, tier),
+            None => "Something went wrong! Pleasse let the boss know!".into(),
+        }
+    }
+}
+
+impl FromStr for Pbta {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "pbta" => Ok(Pbta { arg: None, total: 0.0, tier: None }),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Pbta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tier {
+            Some(tier) => write!(f, "{} -> {}", self.total, tier.to_string().to_lowercase()),
+            None => write!(f, "Not resolved yet"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CortexPick {
+    Total,
+    Effect,
+}
+
+// Cortex Prime: add up the best dice for the total and set aside another one as the effect die. 1s are hitches and can't be used for either
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cortex {
+    pub pick: CortexPick,
+    pub arg: Option<Argument>,
+    pub res: Option<Pool>,
+    pub effect: u16,
+    pub hitches: Option<Pool>,
+}
+
+impl Cortex {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let number = match argument {
+            Argument::Single(number) => number as usize,
+            _ => return Err(RollError::ArgumentError),
+        };
+
+        let (hitches, mut usable): (Vec<Die>, Vec<Die>) = pool.dice().iter().partition(|die| die.equals(1));
+        let effect_die = match self.pick {
+            CortexPick::Total => {
+                // Best results first, and smaller dice first among ties so the bigger ones are left over for the effect
+                usable.sort_by(|d, e| e.result.cmp(&d.result).then(d.sides.cmp(&e.sides)));
+                let rest = usable.split_off(number.min(usable.len()));
+                rest.iter().map(|die| die.sides).max()
+            },
+            CortexPick::Effect => {
+                // Biggest die first, and the lowest result among those so the total loses as little as possible
+                usable.sort_by(|d, e| e.sides.cmp(&d.sides).then(d.result.cmp(&e.result)));
+                let effect_die = match usable.len() {
+                    0 => None,
+                    _ => Some(usable.remove(0).sides),
+                };
+                usable.sort_by_key(|die| Reverse(die.result));
+                usable.truncate(number);
+                effect_die
+            },
+        };
+
+        Ok(Cortex {
+            pick: self.pick,
+            arg: Some(argument),
+            res: Some(Pool::new_from_dice(&usable)),
+            // With no dice left over, the effect die defaults to a d4
+            effect: effect_die.unwrap_or(4),
+            hitches: Some(Pool::new_from_dice(&hitches)),
+        })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> RollValue {
+        self.res.as_ref().unwrap_or(&Pool::empty()).value()
+    }
+
+    pub fn description(&self) -> String {
+        let number = match self.arg {
+            Some(Argument::Single(number)) => number,
+            _ => 2,
+        };
+        match self.pick {
+            CortexPick::Total => format!("Add up the best {} dice that aren't 1s, then take the biggest die left as the effect die", number),
+            CortexPick::Effect => format!("Take the biggest die that isn't a 1 as the effect die, then add up the best {} dice left", number),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        let total = self.res.as_ref().unwrap_or(&Pool::empty()).clone();
+        let hitches = self.hitches.as_ref().unwrap_or(&Pool::empty()).clone();
+        format!("Total: {} -> {}\nEffect die: d{}\nHitches: {}{}", total, total.value(), self.effect, hitches, self.hitch_string())
+    }
+
+    fn hitch_string(&self) -> String {
+        let hitches = self.hitches.as_ref().map_or(0, |pool| pool.dice().len());
+        match (hitches, self.res.as_ref().map_or(0, |pool| pool.dice().len())) {
+            (0, _) => String::new(),
+            (_, 0) => ", botch!".into(),
+            (hitches, _) => format!(", {} hitch(es)", hitches),
+        }
+    }
+}
+
+impl FromStr for Cortex {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pick = match s.trim() {
+            "ct" => CortexPick::Total,
+            "ce" => CortexPick::Effect,
+            _ => return Err(RollError::SymbolError(s.into())),
+        };
+
+        Ok(Cortex { pick, arg: None, res: None, effect: 4, hitches: None })
+    }
+}
+
+impl fmt::Display for Cortex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.res {
+            Some(pool) => write!(f, "total {}, effect die d{}{}", pool, self.effect, self.hitch_string()),
+            None => write!(f, "Not resolved yet"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IronswornScore {
+    Action,
+    Progress,
+    Momentum,
+}
+
+// Ironsworn: the score beats each of the two challenge dice it's greater than, with one for a weak hit and both for a strong hit.
+// The challenge dice are always the last two dice in the pool, and for action rolls the action die comes before them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ironsworn {
+    pub kind: IronswornScore,
+    pub arg: Option<Argument>,
+    pub res: Option<Pool>,
+    pub score: u16,
+    pub tier: Option<PbtaTier>,
+}
+
+impl Ironsworn {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let number = match argument {
+            Argument::Single(number) => number,
+            _ => return Err(RollError::ArgumentError),
+        };
+        let dice = pool.dice();
+        if dice.len() < 2 {
+            return Err(RollError::ArgumentError);
+        }
+
+        // Action scores are capped at 10, and so are progress and momentum, which can't go any higher anyway
+        let score = match self.kind {
+            IronswornScore::Action if dice.len() == 3 => dice[0].result.saturating_add(number).min(10),
+            IronswornScore::Action => return Err(RollError::ArgumentError),
+            IronswornScore::Progress | IronswornScore::Momentum => number.min(10),
+        };
+        let tier = match dice[dice.len() - 2..].iter().filter(|die| score > die.result).count() {
+            2 => PbtaTier::StrongHit,
+            1 => PbtaTier::WeakHit,
+            _ => PbtaTier::Miss,
+        };
+
+        Ok(Ironsworn { kind: self.kind, arg: Some(argument), res: Some(pool), score, tier: Some(tier) })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        match self.tier {
+            Some(_) => Ok(RollValue::Decimal(self.score as f64)),
+            None => Err(RollError::NotResolvedError),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let number = match self.arg {
+            Some(Argument::Single(number)) => number,
+            _ => 0,
+        };
+        match self.kind {
+            IronswornScore::Action => format!("Add {} to the action die, up to 10, and compare it to the challenge dice", number),
+            IronswornScore::Progress => format!("Compare a progress score of {} to the challenge dice", number),
+            IronswornScore::Momentum => format!("Burn momentum: compare a score of {} to the challenge dice instead", number),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        format!("{} -> {}", self.res.as_ref().unwrap_or(&Pool::empty()), self)
+    }
+
+    // Both challenge dice showing the same number is a match, whatever the outcome
+    pub fn matched(&self) -> bool {
+        match &self.res {
+            Some(pool) if pool.dice().len() >= 2 => {
+                let dice = pool.dice();
+                dice[dice.len() - 1].result == dice[dice.len() - 2].result
+            },
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Ironsworn {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s.trim() {
+            "is" => IronswornScore::Action,
+            "isp" => IronswornScore::Progress,
+            "ism" => IronswornScore::Momentum,
+            _ => return Err(RollError::SymbolError(s.into())),
+        };
+
+        Ok(Ironsworn { kind, arg: None, res: None, score: 0, tier: None })
+    }
+}
+
+impl fmt::Display for Ironsworn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pool, tier) = match (&self.res, self.tier) {
+            (Some(pool), Some(tier)) => (pool, tier),
+            _ => return write!(f, "Not resolved yet"),
+        };
+        let kind = match self.kind {
+            IronswornScore::Action => "action",
+            IronswornScore::Progress => "progress",
+            IronswornScore::Momentum => "momentum",
+        };
+        let challenge: Vec<u16> = pool.dice()[pool.dice().len() - 2..].iter().map(|die| die.result).collect();
+        let matched = if self.matched() { ", match!" } else { "" };
+
+        write!(f, "{} {} vs {:?} -> {}{}", kind, self.score, challenge, tier.to_string().to_lowercase(), matched)
+    }
+}
+
+// One-Roll Engine sets. Takes the number of hard dice, which always come up as the highest face, or an array of hard and wiggle dice
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ore {
+    pub arg: Option<Argument>,
+    pub res: Option<Pool>,
+    pub sets: Option<OreValue>,
+}
+
+impl Ore {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let (hard, wiggle) = match &argument {
+            Argument::Single(hard) => (*hard, 0),
+            Argument::Array(dice) if dice.len() == 2 => (dice[0], dice[1]),
+            _ => return Err(RollError::ArgumentError),
+        };
+        if pool.dice().len() + hard as usize > MAX_DICE {
+            return Err(RollError::OverflowError);
+        }
+
+        let sides = pool.sides_max();
+        let mut dice = pool.dice().clone();
+        dice.extend((0..hard).map(|_| Die::new(sides, sides)));
+        let results: Vec<u16> = dice.iter().map(|die| die.result).collect();
+
+        Ok(Ore { arg: Some(argument), res: Some(Pool::new_from_dice(&dice)), sets: Some(OreValue::new(&results, wiggle)) })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        Ok(RollValue::Ore(self.sets.clone().ok_or(RollError::NotResolvedError)?))
+    }
+
+    pub fn description(&self) -> String {
+        match &self.arg {
+            Some(Argument::Single(0)) => "Group matching dice into sets".into(),
+            Some(Argument::Single(hard)) => format!("Add {} hard di(c)e and group matching dice into sets", hard),
+            Some(Argument::Array(dice)) if dice.len() == 2 => format!("Add {} hard and {} wiggle di(c)e and group matching dice into sets", dice[0], dice[1]),
+            _ => "Something went wrong! Pleasse let the boss know!".into(),
+        }
+    }
+
+    pub fn verbose(&self) -> String {
+        match &self.sets {
+            Some(sets) => format!("{} -> {}\nWaste: {:?}", self.res.as_ref().unwrap_or(&Pool::empty()), sets, sets.waste),
+            None => "Something went wrong! Pleasse let the boss know!".into(),
+        }
+    }
+}
+
+impl FromStr for Ore {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "ore" => Ok(Ore { arg: None, res: None, sets: None }),
+            _ => Err(RollError::SymbolError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Ore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.sets {
+            Some(sets) => write!(f, "sets {}", sets),
+            None => write!(f, "Not resolved yet"),
+        }
+    }
+}
+
+// Call of Cthulhu percentile rolls: bonus dice take the lowest of the rolls the tens dice make, penalty dice the highest
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coc {
+    pub penalty: bool,
+    pub arg: Option<Argument>,
+    pub res: Option<Pool>,
+    pub outcome: Option<CocValue>,
+}
+
+impl Coc {
+    pub fn apply(&self, pool: Pool, argument: Argument) -> Result<Self, RollError> {
+        let skill = match argument {
+            Argument::Single(skill) => skill,
+            _ => return Err(RollError::ArgumentError),
+        };
+        if !pool.is_percentile() { return Err(RollError::ArgumentError); }
+
+        let rolls = pool.percentile_rolls();
+        let roll = match self.penalty {
+            false => rolls.iter().min(),
+            true => rolls.iter().max(),
+        }.copied().ok_or(RollError::MissingPoolError)?;
+
+        Ok(Coc { penalty: self.penalty, arg: Some(argument), res: Some(pool), outcome: Some(CocValue::new(roll, skill)) })
+    }
+
+    pub fn pool(self) -> Result<Pool, RollError> {
+        self.res.ok_or(RollError::NotResolvedError)
+    }
+
+    pub fn value(&self) -> Result<RollValue, RollError> {
+        Ok(RollValue::Coc(self.outcome.clone().ok_or(RollError::NotResolvedError)?))
+    }
+
+    pub fn description(&self) -> String {
+        let pick = match self.penalty {
+            false => "lowest",
+            true => "highest",
+        };
+        format!("Take the {} roll and compare it to a skill of {}", pick, self.arg.as_ref().unwrap_or(&Argument::Single(0)))
+    }
+
+    pub fn verbose(&self) -> String {
+        let rolls = self.res.as_ref().map_or(vec![], |pool| pool.percentile_rolls());
+        match &self.outcome {
+            Some(outcome) => format!(
+                "Rolls: {:?} -> {} against {} (hard {}, extreme {}) -> {}",
+                rolls, outcome.roll, outcome.skill, outcome.skill / 2, outcome.skill / 5, outcome.level
+            ),
+            None => "Something went wrong! Pleasse let the boss know!".into(),
+        }
+    }
+}
+
+impl FromStr for Coc {
+    type Err = RollError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let penalty = match s.trim() {
+            "coc" => false,
+            "cocp" => true,
+            _ => return Err(RollError::SymbolError(s.into())),
+        };
+
+        Ok(Coc { penalty, arg: None, res: None, outcome: None })
+    }
+}
+
+impl fmt::Display for Coc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Some(outcome) => write!(f, "vs {} -> {}", outcome.skill, outcome),
+            None => write!(f, "Not resolved yet"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::dice::die::Die;
+    use super::*;
+
+    #[test]
+    fn test_argument_range() {
+        assert_eq!(Argument::from_decimal(12.0).unwrap(), Argument::Single(12));
+        assert!(matches!(Argument::from_decimal(-1.0), Err(RollError::ArgumentError)));
+        assert!(matches!(Argument::from_decimal(f64::NAN), Err(RollError::ArgumentError)));
+        assert!(matches!(Argument::from_decimal(f64::INFINITY), Err(RollError::ArgumentError)));
+        assert!(matches!(Argument::from_decimal(70000.0), Err(RollError::OverflowError)));
+
+        assert!(matches!("70000".parse::<Argument>(), Err(RollError::OverflowError)));
+        assert!(matches!("[6, 70000]".parse::<Argument>(), Err(RollError::OverflowError)));
+        assert!(matches!("x".parse::<Argument>(), Err(RollError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_cap_max() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let pool = Pool::new_from_dice(&[Die::new(6, 1), Die::new(6, 4), Die::new(6, 6)]);
+        let cap: Cap = "c".parse().unwrap();
+        let capped = Operator::Cap(cap).apply(RollToken::Dice(Dice { pool: Some(pool) }), Argument::Single(5), &mut rng).unwrap();
+
+        assert_eq!(capped.value().unwrap(), RollValue::Decimal(10.0));
+        assert_eq!(capped.verbose(), "Cap 1 di(c)e showing [6], result: [1, 4, 5]");
+    }
+
+    #[test]
+    fn test_drop() {
+        let pool = Pool::new_from_dice(&[Die::new(6, 3), Die::new(6, 1), Die::new(6, 5), Die::new(6, 1)]);
+        let dropped = Drop::Low { arg: None, res: None, dropped: None }.apply(pool, Argument::Single(1)).unwrap();
+
+        assert_eq!(dropped.value().unwrap(), RollValue::Decimal(9.0));
+        assert_eq!(dropped.verbose(), "Drop 1 lowest -> [~~1~~], result: [3, 5, 1]");
+        assert_eq!(format!("{}", dropped), "drop lowest 1 -> [3, 5, 1]");
+    }
+
+    #[test]
+    fn test_cap_min() {
+        let pool = Pool::new_from_dice(&[Die::new(6, 1), Die::new(6, 1), Die::new(6, 3)]);
+        let capped = Cap::Min { arg: None, res: None, capped: None }.apply(pool, Argument::Single(2)).unwrap();
+
+        assert_eq!(capped.value().unwrap(), RollValue::Decimal(7.0));
+        assert_eq!(capped.description(), "Cap dice at a minimum of 2");
+    }
+
+    #[test]
+    fn test_cap_by_sides() {
+        let pool = Pool::new_from_dice(&[Die::new(6, 6), Die::new(8, 8), Die::new(8, 2)]);
+        let capped = Cap::Max { arg: None, res: None, capped: None }.apply(pool.clone(), Argument::Array(vec![4, 5])).unwrap();
+
+        assert_eq!(capped.pool().unwrap().dice(), &vec![Die::new(6, 4), Die::new(8, 5), Die::new(8, 2)]);
+        assert!(Cap::Max { arg: None, res: None, capped: None }.apply(pool, Argument::Array(vec![4, 5, 6])).is_err());
+    }
+}
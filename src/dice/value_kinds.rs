@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use super::{
     genesymbols::GeneSymbol,
+    l5rsymbols::L5rSymbol,
 };
 use std::fmt;
 
@@ -43,7 +44,7 @@ impl GenesysValue {
                         Some(_) => { tally.entry(cancel).and_modify(|counter| *counter -= 1).or_insert(1); }
                     }
                 },
-                GeneSymbol::Triumph | GeneSymbol::Despair => { tally.entry(*symbol).and_modify(|counter| *counter += 1).or_insert(1); },
+                GeneSymbol::Triumph | GeneSymbol::Despair | GeneSymbol::LightSide | GeneSymbol::DarkSide => { tally.entry(*symbol).and_modify(|counter| *counter += 1).or_insert(1); },
                 GeneSymbol::Blank => continue,
             };
         }
@@ -63,26 +64,141 @@ impl GenesysValue {
         symbols
     }
 
+    // Triumphs count as successes and despairs as failures, as per Genesys Core Rulebook p. 12
+    pub fn net_successes(&self) -> i16 {
+        let count = |symbol| *self.tally.get(&symbol).unwrap_or(&0) as i16;
+
+        count(GeneSymbol::Success) + count(GeneSymbol::Triumph) - count(GeneSymbol::Failure) - count(GeneSymbol::Despair)
+    }
+
     pub fn add(self, other: GenesysValue) -> Self {
         let symbols = vec![self.to_vector(), other.to_vector()];
 
         GenesysValue::new(&symbols)
     }
+
+    fn count(&self, symbol: GeneSymbol) -> u8 {
+        *self.tally.get(&symbol).unwrap_or(&0)
+    }
+
+    // What the roll means at the table: whether it succeeded, what side effects it has, and any triumphs, despairs or force pips.
+    // Symbols that have an emoji set are shown with it
+    pub fn summary(&self, emoji: &HashMap<GeneSymbol, String>) -> String {
+        let mark = |symbol: GeneSymbol| match emoji.get(&symbol) {
+            Some(emoji) => format!("{} ", emoji),
+            None => String::new(),
+        };
+
+        let mut summary = match self.net_successes() {
+            net if net > 0 => format!("{}Succeeded", mark(GeneSymbol::Success)),
+            _ => format!("{}Failed", mark(GeneSymbol::Failure)),
+        };
+        for side_effect in [GeneSymbol::Advantage, GeneSymbol::Threat] {
+            if self.count(side_effect) > 0 {
+                summary = format!("{} with {}{} {}", summary, mark(side_effect), self.count(side_effect), side_effect.name(self.count(side_effect)));
+            }
+        }
+        for callout in [GeneSymbol::Triumph, GeneSymbol::Despair] {
+            match self.count(callout) {
+                0 => (),
+                1 => summary = format!("{} | {}{}!", summary, mark(callout), callout),
+                number => summary = format!("{} | {}{} {}!", summary, mark(callout), number, callout.name(number)),
+            }
+        }
+        for pip in [GeneSymbol::LightSide, GeneSymbol::DarkSide] {
+            if self.count(pip) > 0 {
+                summary = format!("{} | {}{} {}", summary, mark(pip), self.count(pip), pip.name(self.count(pip)));
+            }
+        }
+
+        summary
+    }
 }
 
 impl fmt::Display for GenesysValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let tally_str = self.tally.iter().fold(
-            String::new(), |s, (key, val)| {
-                if *val == 0 { s } else {
-                    let plural = match val {
-                        1 => "",
-                        _ => match key {
-                            GeneSymbol::Success => "es",
-                            _ => "s"
-                        }
-                    };
-                    format!("{}{} {:?}{}, ", s, val, key, plural)
+        // Force pips are reported on their own since they have nothing to do with success or failure
+        let (force, tally): (Vec<GeneSymbol>, Vec<GeneSymbol>) = GeneSymbol::ORDER.iter()
+            .filter(|&&symbol| self.count(symbol) > 0)
+            .partition(|symbol| matches!(symbol, GeneSymbol::LightSide | GeneSymbol::DarkSide));
+        let describe = |symbols: Vec<GeneSymbol>| symbols.iter()
+            .map(|&symbol| format!("{} {}", self.count(symbol), symbol.name(self.count(symbol))))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match (tally.len(), force.len()) {
+            (0, 0) => write!(f, "Blank"),
+            (_, 0) => write!(f, "{}", describe(tally)),
+            (0, _) => write!(f, "Force: {}", describe(force)),
+            _ => write!(f, "{} | Force: {}", describe(tally), describe(force)),
+        }
+    }
+}
+
+// L5R symbols don't cancel each other out, and which dice to keep is up to the player, so this is just a running count
+#[derive(Clone, Debug, PartialEq)]
+pub struct L5rValue {
+    pub tally: HashMap<L5rSymbol, u8>
+}
+
+impl L5rValue {
+    pub fn new(symbols: &[Vec<L5rSymbol>]) -> Self {
+        let mut tally = HashMap::new();
+
+        for symbol in symbols.iter().flatten() {
+            if *symbol != L5rSymbol::Blank {
+                tally.entry(*symbol).and_modify(|counter| *counter += 1).or_insert(1);
+            }
+        }
+
+        L5rValue { tally }
+    }
+
+    fn to_vector(&self) -> Vec<L5rSymbol> {
+        let mut symbols = vec![];
+
+        for (&symbol, &number) in self.tally.iter() {
+            for _ in 0..number {
+                symbols.push(symbol);
+            }
+        }
+
+        symbols
+    }
+
+    // Explosive successes are successes too
+    pub fn successes(&self) -> i16 {
+        let count = |symbol| *self.tally.get(&symbol).unwrap_or(&0) as i16;
+
+        count(L5rSymbol::Success) + count(L5rSymbol::ExplosiveSuccess)
+    }
+
+    pub fn add(self, other: L5rValue) -> Self {
+        let symbols = vec![self.to_vector(), other.to_vector()];
+
+        L5rValue::new(&symbols)
+    }
+}
+
+impl fmt::Display for L5rValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = [L5rSymbol::ExplosiveSuccess, L5rSymbol::Success, L5rSymbol::Opportunity, L5rSymbol::Strife];
+        let tally_str = order.iter().fold(
+            String::new(), |s, key| {
+                match self.tally.get(key).unwrap_or(&0) {
+                    0 => s,
+                    &val => {
+                        let name = match (key, val) {
+                            (L5rSymbol::ExplosiveSuccess, 1) => "Explosive Success",
+                            (L5rSymbol::ExplosiveSuccess, _) => "Explosive Successes",
+                            (L5rSymbol::Success, 1) => "Success",
+                            (L5rSymbol::Success, _) => "Successes",
+                            (L5rSymbol::Opportunity, 1) => "Opportunity",
+                            (L5rSymbol::Opportunity, _) => "Opportunities",
+                            _ => "Strife",
+                        };
+                        format!("{}{} {}, ", s, val, name)
+                    }
                 }
             }
         );
@@ -93,3 +209,112 @@ impl fmt::Display for GenesysValue {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SuccessLevel {
+    Fumble,
+    Failure,
+    Regular,
+    Hard,
+    Extreme,
+    Critical,
+}
+
+impl SuccessLevel {
+    // Call of Cthulhu 7e: 01 is always a critical, 100 is always a fumble, and so is 96 or more when the skill is below 50
+    pub fn coc(roll: u16, skill: u16) -> Self {
+        match roll {
+            1 => SuccessLevel::Critical,
+            r if r <= skill / 5 => SuccessLevel::Extreme,
+            r if r <= skill / 2 => SuccessLevel::Hard,
+            r if r <= skill && r < 100 => SuccessLevel::Regular,
+            100 => SuccessLevel::Fumble,
+            r if r >= 96 && skill < 50 => SuccessLevel::Fumble,
+            _ => SuccessLevel::Failure,
+        }
+    }
+
+    // Counts failure as zero and each level of success above it as one more
+    pub fn rank(&self) -> i8 {
+        *self as i8 - SuccessLevel::Failure as i8
+    }
+}
+
+impl fmt::Display for SuccessLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuccessLevel::Fumble => write!(f, "Fumble!"),
+            SuccessLevel::Failure => write!(f, "Failure"),
+            SuccessLevel::Regular => write!(f, "Regular Success"),
+            SuccessLevel::Hard => write!(f, "Hard Success"),
+            SuccessLevel::Extreme => write!(f, "Extreme Success"),
+            SuccessLevel::Critical => write!(f, "Critical Success!"),
+        }
+    }
+}
+
+// The percentile roll that was picked and how well it did against the skill
+#[derive(Clone, Debug, PartialEq)]
+pub struct CocValue {
+    pub roll: u16,
+    pub skill: u16,
+    pub level: SuccessLevel,
+}
+
+impl CocValue {
+    pub fn new(roll: u16, skill: u16) -> Self {
+        CocValue { roll, skill, level: SuccessLevel::coc(roll, skill) }
+    }
+}
+
+impl fmt::Display for CocValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.level, self.roll)
+    }
+}
+
+// One-Roll Engine sets of matching faces, widest first and tallest first among equally wide ones.
+// Wiggle dice are left for the player to set, so they're only counted
+#[derive(Clone, Debug, PartialEq)]
+pub struct OreValue {
+    pub sets: Vec<(u16, u16)>,
+    pub waste: Vec<u16>,
+    pub wiggle: u16,
+}
+
+impl OreValue {
+    pub fn new(results: &[u16], wiggle: u16) -> Self {
+        let mut faces = BTreeMap::new();
+        for &result in results {
+            faces.entry(result).and_modify(|width| *width += 1).or_insert(1u16);
+        }
+
+        let waste = faces.iter().filter(|&(_, &width)| width == 1).map(|(&height, _)| height).collect();
+        let mut sets: Vec<(u16, u16)> = faces.into_iter()
+            .filter(|&(_, width)| width > 1)
+            .map(|(height, width)| (width, height))
+            .collect();
+        sets.sort_by(|a, b| b.cmp(a));
+
+        OreValue { sets, waste, wiggle }
+    }
+
+    pub fn widest(&self) -> u16 {
+        self.sets.first().map_or(0, |&(width, _)| width)
+    }
+}
+
+impl fmt::Display for OreValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sets = match self.sets.len() {
+            0 => "No sets".to_string(),
+            _ => self.sets.iter().map(|(width, height)| format!("{}x{}", width, height)).collect::<Vec<_>>().join(", "),
+        };
+
+        match self.wiggle {
+            0 => write!(f, "{}", sets),
+            1 => write!(f, "{} + 1 wiggle die", sets),
+            wiggle => write!(f, "{} + {} wiggle dice", sets, wiggle),
+        }
+    }
+}
@@ -85,7 +85,7 @@ struct Funsies;
 #[group]
 #[description = "Commands related to rolling dice.\n\n
 Use `roll` for generic dice rolls or one of the specialized functions to use simplified syntax tailored to the system."]
-#[commands(roll, modify, reroll, pastrolls, exroll, genroll, l5r, s3roll, sr, cofd, verbose)]
+#[commands(roll, modify, push, burn, reroll, replay, odds, sim, pastrolls, exroll, genroll, l5r, s3roll, sr, cofd, wod, fate, dnd, pbta, blades, swade, yz, cortex, coc, wfrp, ironsworn, verbose)]
 struct Dice;
 
 #[group]